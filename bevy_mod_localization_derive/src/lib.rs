@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse, Attribute, DeriveInput, Lit, Meta};
@@ -13,7 +15,22 @@ use syn::{parse, Attribute, DeriveInput, Lit, Meta};
 /// ````
 ///
 /// The folder path works like an asset path, i.e. by default, `/assets` is the root.
-#[proc_macro_derive(LocalizationFolder, attributes(folder_path))]
+///
+/// Add the `#[embed]` attribute to include the `.ftl` files of the folder in the binary:
+///
+/// ```
+/// #[derive(LocalizationFolder)]
+/// #[folder_path = "path/to/folder"]
+/// #[embed]
+/// struct ExampleFolder;
+/// ```
+///
+/// The files are then read from `/assets/path/to/folder` at compile time.
+/// Use `#[embed = "path/to/assets"]` if your asset folder is not located at `/assets`.
+///
+/// Every file is included with `include_str!`, so changing it triggers a rebuild.
+/// Adding or removing a file is not tracked, see the `LocalizationFolder` docs.
+#[proc_macro_derive(LocalizationFolder, attributes(folder_path, embed))]
 pub fn localization_folder_derive(input: TokenStream) -> TokenStream {
     // Construct a representation of Rust code as a syntax tree
     // that we can manipulate
@@ -28,9 +45,21 @@ fn impl_localization_folder(ast: &DeriveInput) -> TokenStream {
     let name = &ast.ident;
     let folder_path = get_folder_path(&ast.attrs);
 
+    let embedded_sources = get_embed_asset_folder(&ast.attrs).map(|asset_folder| {
+        let sources = get_embedded_sources(&asset_folder, &folder_path)
+            .into_iter()
+            .map(|(lang_id, path)| quote! { (#lang_id, include_str!(#path)) });
+
+        quote! {
+            const EMBEDDED_SOURCES: Option<&'static [(&'static str, &'static str)]> =
+                Some(&[#(#sources),*]);
+        }
+    });
+
     let gen = quote! {
         impl LocalizationFolder for #name {
             const FOLDER_PATH: &'static str = #folder_path;
+            #embedded_sources
         }
     };
     gen.into()
@@ -70,3 +99,70 @@ fn get_folder_path(attributes: &[Attribute]) -> String {
 
     panic!("No #[folder_path = \"path/to/folder\"]` attribute found, required for `#[derive(LocalizationFolder)]`!");
 }
+
+/// Get the asset folder to embed the localization files from.
+///
+/// This is extracted from an `#[embed]` or `#[embed = "path/to/assets"]` attribute.
+/// Returns `None` if the folder should not be embedded.
+fn get_embed_asset_folder(attributes: &[Attribute]) -> Option<String> {
+    for attribute in attributes.iter().filter_map(|attr| attr.parse_meta().ok()) {
+        if attribute
+            .path()
+            .get_ident()
+            .map(|i| i != "embed")
+            .unwrap_or(true)
+        {
+            continue;
+        }
+
+        return match attribute {
+            Meta::Path(_) => Some("assets".to_string()),
+            Meta::NameValue(name_value) => match name_value.lit {
+                Lit::Str(lit_str) => Some(lit_str.value()),
+                _ => {
+                    panic!("`embed` attribute must take the form `#[embed = \"path/to/assets\"]`.")
+                }
+            },
+            Meta::List(_) => {
+                panic!("`embed` attribute must take the form `#[embed]` or `#[embed = \"path/to/assets\"]`.")
+            }
+        };
+    }
+
+    None
+}
+
+/// Find the localization files to embed.
+///
+/// Proc macros can't track directories on stable Rust, so only the found files are
+/// tracked through their `include_str!`.
+///
+/// Returns the language ID and the absolute path of every `.ftl` file in the folder.
+fn get_embedded_sources(asset_folder: &str, folder_path: &str) -> Vec<(String, String)> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .expect("`#[embed]` requires the `CARGO_MANIFEST_DIR` environment variable.");
+    let folder: PathBuf = Path::new(&manifest_dir)
+        .join(asset_folder)
+        .join(folder_path.trim_start_matches('/'));
+
+    let entries = std::fs::read_dir(&folder).unwrap_or_else(|err| {
+        panic!(
+            "Failed to read the localization folder `{}` to embed: {err}",
+            folder.display()
+        )
+    });
+
+    let mut sources: Vec<(String, String)> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map(|ext| ext == "ftl").unwrap_or(false))
+        .filter_map(|path| {
+            let lang_id = path.file_stem()?.to_str()?.to_string();
+            let path = path.to_str()?.to_string();
+            Some((lang_id, path))
+        })
+        .collect();
+
+    // Keep the generated code deterministic
+    sources.sort();
+    sources
+}
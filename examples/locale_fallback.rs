@@ -12,9 +12,9 @@
 //!
 //! This example uses the following configuration:
 //!
-//! - [`Locale`]: `en-US`
-//! - [`LocaleFallbackMap`]: { `en-US` -> `en-GB` }
-//! - [`LocaleDefaultFallback`]: `de`
+//! - The [`Locale`] is `en-US`.
+//! - The [`LocaleFallbackMap`] maps `en-US` to `en-GB`.
+//! - The [`LocaleDefaultFallback`] is `de`.
 //!
 //! The functionality is demonstrated with three messages:
 //!
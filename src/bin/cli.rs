@@ -0,0 +1,365 @@
+//! Command-line tools for the localization files of Bevy apps.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use bevy_mod_localization::{
+    coverage::{folder_coverage, write_report, ReportFormat},
+    extract::{compare_with_folders, extract_from_dir, Extraction},
+    gettext::{export_folder, import_folder},
+    lint::{lint_folder, Severity},
+};
+use unic_langid::LanguageIdentifier;
+
+const USAGE: &str = "\
+Usage: bevy_mod_localization <COMMAND> [OPTIONS] <FOLDER>...
+
+Commands:
+  lint       Check the localization files of the folders for mistakes
+  coverage   Report how much of the folders is translated into each locale
+  extract    Compare the message IDs used in the Rust source folders with the localization files
+  po-export  Export the folders to gettext PO files for translation
+  po-import  Import the translated gettext PO files into the folders
+
+Options:
+  --reference <LOCALE>  The locale that the other locales are compared to
+  --fallbacks <FILE>    Use the default fallback of a `.fallbacks.ron` config as reference
+                        One of them is required, except for po-import
+  --deny-warnings       Exit with an error if there are warnings (lint)
+  --format <FORMAT>     The format of the report: table, json or markdown [default: table] (coverage)
+  --assets <FOLDER>     The asset folder containing the localization folders [default: assets] (extract)
+  --write-stubs         Add the missing messages to the reference locale (extract)
+  --po <FOLDER>         The folder containing a PO folder for each localization folder [default: po] (po-export, po-import)
+  -h, --help            Print this help";
+
+/// The commands that compare the locales to a reference locale.
+const REFERENCE_COMMANDS: [&str; 4] = ["lint", "coverage", "extract", "po-export"];
+
+/// The parsed command-line arguments shared by all commands.
+struct Options {
+    folders: Vec<String>,
+    reference_locale: Option<LanguageIdentifier>,
+    deny_warnings: bool,
+    format: ReportFormat,
+    asset_folder: String,
+    write_stubs: bool,
+    po_folder: String,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Options {
+            folders: Vec::new(),
+            reference_locale: None,
+            deny_warnings: false,
+            format: ReportFormat::default(),
+            asset_folder: "assets".to_string(),
+            write_stubs: false,
+            po_folder: "po".to_string(),
+        };
+
+        let mut args = args.into_iter();
+        let mut reference_locale = None;
+        let mut fallbacks_path = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--reference" => {
+                    let locale = args.next().ok_or("--reference requires a locale")?;
+                    reference_locale = Some(
+                        locale
+                            .parse()
+                            .map_err(|_| format!("invalid reference locale `{locale}`"))?,
+                    );
+                }
+                "--fallbacks" => {
+                    fallbacks_path = Some(args.next().ok_or("--fallbacks requires a file")?);
+                }
+                "--deny-warnings" => options.deny_warnings = true,
+                "--format" => {
+                    let format = args.next().ok_or("--format requires a format")?;
+                    options.format = format.parse()?;
+                }
+                "--assets" => {
+                    options.asset_folder = args.next().ok_or("--assets requires a folder")?;
+                }
+                "--write-stubs" => options.write_stubs = true,
+                "--po" => {
+                    options.po_folder = args.next().ok_or("--po requires a folder")?;
+                }
+                _ if arg.starts_with('-') => return Err(format!("unknown option `{arg}`")),
+                _ => options.folders.push(arg),
+            }
+        }
+
+        if options.folders.is_empty() {
+            return Err("no folder given".to_string());
+        }
+
+        // An explicit reference takes precedence over the default fallback of the config
+        if reference_locale.is_some() {
+            options.reference_locale = reference_locale;
+        } else if let Some(fallbacks_path) = fallbacks_path {
+            options.reference_locale = Some(read_default_fallback(&fallbacks_path)?);
+        }
+
+        Ok(options)
+    }
+
+    /// The reference locale, which is checked for the [`REFERENCE_COMMANDS`] in `main`.
+    fn reference_locale(&self) -> &LanguageIdentifier {
+        self.reference_locale
+            .as_ref()
+            .expect("the reference locale is required for this command")
+    }
+}
+
+/// Read the default fallback locale from a `.fallbacks.ron` config file.
+fn read_default_fallback(path: &str) -> Result<LanguageIdentifier, String> {
+    use bevy_mod_localization::locale::LocaleFallbackConfig;
+
+    let config = std::fs::read_to_string(path)
+        .map_err(|error| format!("failed to read `{path}`: {error}"))?;
+    let config: LocaleFallbackConfig =
+        ron::de::from_str(&config).map_err(|error| format!("failed to parse `{path}`: {error}"))?;
+
+    config.default_fallback.ok_or_else(|| {
+        format!("`{path}` has no default fallback, pass the reference with --reference instead")
+    })
+}
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+
+    let command = match args.next() {
+        Some(command) if command != "-h" && command != "--help" => command,
+        _ => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+    };
+
+    let options = match Options::parse(args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if REFERENCE_COMMANDS.contains(&command.as_str()) && options.reference_locale.is_none() {
+        eprintln!("error: `{command}` requires --reference or --fallbacks\n\n{USAGE}");
+        return ExitCode::FAILURE;
+    }
+
+    match command.as_str() {
+        "lint" => lint(&options),
+        "coverage" => coverage(&options),
+        "extract" => extract(&options),
+        "po-export" => po_export(&options),
+        "po-import" => po_import(&options),
+        _ => {
+            eprintln!("error: unknown command `{command}`\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn lint(options: &Options) -> ExitCode {
+    let mut errors = 0;
+    let mut warnings = 0;
+
+    for folder in &options.folders {
+        let diagnostics = match lint_folder(folder, options.reference_locale()) {
+            Ok(diagnostics) => diagnostics,
+            Err(error) => {
+                eprintln!("error: failed to read `{folder}`: {error}");
+                errors += 1;
+                continue;
+            }
+        };
+
+        for diagnostic in diagnostics {
+            match diagnostic.severity() {
+                Severity::Error => errors += 1,
+                Severity::Warning => warnings += 1,
+            }
+
+            println!("{diagnostic}");
+        }
+    }
+
+    println!("{errors} errors, {warnings} warnings");
+
+    if errors > 0 || (options.deny_warnings && warnings > 0) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn coverage(options: &Options) -> ExitCode {
+    let mut coverages = Vec::new();
+
+    for folder in &options.folders {
+        match folder_coverage(folder, options.reference_locale()) {
+            Ok(coverage) => coverages.push(coverage),
+            Err(error) => {
+                eprintln!("error: failed to read `{folder}`: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let mut report = String::new();
+    // Writing to a string can't fail
+    let _ = write_report(&mut report, &coverages, options.format);
+    print!("{report}");
+
+    ExitCode::SUCCESS
+}
+
+fn extract(options: &Options) -> ExitCode {
+    let mut extraction = Extraction::default();
+
+    for folder in &options.folders {
+        match extract_from_dir(folder) {
+            Ok(folder_extraction) => {
+                extraction.usages.extend(folder_extraction.usages);
+                extraction
+                    .folder_paths
+                    .extend(folder_extraction.folder_paths);
+                extraction.errors.extend(folder_extraction.errors);
+            }
+            Err(error) => {
+                eprintln!("error: failed to read `{folder}`: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    // The message IDs used in these files are missing from the reports
+    for error in &extraction.errors {
+        eprintln!("error: failed to parse {error}");
+    }
+
+    let reports = match compare_with_folders(
+        &extraction,
+        &options.asset_folder,
+        options.reference_locale(),
+    ) {
+        Ok(reports) => reports,
+        Err(error) => {
+            eprintln!("error: failed to read the localization files: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut missing = 0;
+
+    for report in &reports {
+        println!(
+            "{} ({})",
+            report.folder_path,
+            report.reference_path.display()
+        );
+
+        for usage in &report.missing {
+            println!(
+                "  missing: `{}` used in {}:{}",
+                usage.message_id,
+                usage.path.display(),
+                usage.line
+            );
+        }
+
+        for id in &report.unused {
+            println!("  unused: `{id}`");
+        }
+
+        if options.write_stubs {
+            if let Err(error) = report.write_stubs() {
+                eprintln!(
+                    "error: failed to write `{}`: {error}",
+                    report.reference_path.display()
+                );
+                return ExitCode::FAILURE;
+            }
+
+            if !report.missing.is_empty() {
+                println!(
+                    "  added {} stubs to {}",
+                    report.missing.len(),
+                    report.reference_path.display()
+                );
+            }
+        } else {
+            missing += report.missing.len();
+        }
+    }
+
+    let unassigned = extraction
+        .usages
+        .iter()
+        .filter(|usage| usage.folder_path.is_none())
+        .count();
+
+    if unassigned > 0 {
+        println!("{unassigned} message IDs could not be assigned to a localization folder");
+    }
+
+    if missing > 0 || !extraction.errors.is_empty() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Get the PO folder of a localization folder, named like the localization folder.
+fn po_folder_of(options: &Options, folder: &str) -> PathBuf {
+    let name = Path::new(folder).file_name().unwrap_or_default();
+    Path::new(&options.po_folder).join(name)
+}
+
+fn po_export(options: &Options) -> ExitCode {
+    for folder in &options.folders {
+        let po_folder = po_folder_of(options, folder);
+
+        match export_folder(folder, options.reference_locale(), &po_folder) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("wrote {}", path.display());
+                }
+            }
+            Err(error) => {
+                eprintln!("error: failed to export `{folder}`: {error}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn po_import(options: &Options) -> ExitCode {
+    for folder in &options.folders {
+        let po_folder = po_folder_of(options, folder);
+
+        match import_folder(&po_folder, folder) {
+            Ok(paths) => {
+                for path in paths {
+                    println!("wrote {}", path.display());
+                }
+            }
+            Err(error) => {
+                eprintln!("error: failed to import `{}`: {error}", po_folder.display());
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,369 @@
+//! Measure how much of a localization folder is translated into each locale.
+//!
+//! Every locale is compared to a reference locale, usually the [`LocaleDefaultFallback`],
+//! see [`default_fallback_coverage`].
+//! The report can also be created with the `coverage` command of the
+//! `bevy_mod_localization` binary:
+//!
+//! ```text
+//! bevy_mod_localization coverage assets/strings/menu --fallbacks assets/locales.fallbacks.ron
+//! ```
+//!
+//! The binary can't know the default fallback of your app, so the reference is required.
+//! Pass it with `--reference`, or pass the `.fallbacks.ron` config of your app
+//! with `--fallbacks` to use its default fallback.
+//!
+//! This module requires the `cli` feature.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, io,
+    path::Path,
+    str::FromStr,
+};
+
+use fluent::FluentResource;
+use fluent_syntax::ast;
+use serde::Serialize;
+use unic_langid::LanguageIdentifier;
+
+use crate::{ftl_files::read_ftl_files, locale::LocaleDefaultFallback};
+
+/// The number of translated messages or attributes, out of those in the reference locale.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CoverageCount {
+    pub translated: usize,
+    pub total: usize,
+}
+
+impl CoverageCount {
+    /// The percentage of translated messages or attributes.
+    ///
+    /// If there is nothing to translate, everything counts as translated.
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.translated as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// The translation coverage of a single locale.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(into = "LocaleReport")]
+pub struct LocaleCoverage {
+    pub locale: LanguageIdentifier,
+    /// The messages with a value.
+    pub messages: CoverageCount,
+    pub attributes: CoverageCount,
+}
+
+impl LocaleCoverage {
+    /// The percentage of translated messages and attributes combined.
+    pub fn percentage(&self) -> f64 {
+        CoverageCount {
+            translated: self.messages.translated + self.attributes.translated,
+            total: self.messages.total + self.attributes.total,
+        }
+        .percentage()
+    }
+}
+
+/// A [`LocaleCoverage`] as written to JSON reports, including the percentage.
+#[derive(Serialize)]
+struct LocaleReport {
+    locale: LanguageIdentifier,
+    messages: CoverageCount,
+    attributes: CoverageCount,
+    percentage: f64,
+}
+
+impl From<LocaleCoverage> for LocaleReport {
+    fn from(coverage: LocaleCoverage) -> Self {
+        Self {
+            // Rounded like in the other report formats
+            percentage: (coverage.percentage() * 10.0).round() / 10.0,
+            locale: coverage.locale,
+            messages: coverage.messages,
+            attributes: coverage.attributes,
+        }
+    }
+}
+
+/// The translation coverage of all locales of a localization folder.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FolderCoverage {
+    #[serde(rename = "folder")]
+    pub folder_path: String,
+    #[serde(rename = "reference")]
+    pub reference_locale: LanguageIdentifier,
+    /// The coverage of every locale in the folder, including the reference locale.
+    pub locales: Vec<LocaleCoverage>,
+}
+
+/// Compute the translation coverage of every locale in the folder.
+///
+/// Only messages and attributes defined in the reference locale are counted,
+/// terms are considered an implementation detail of the translations.
+///
+/// ```
+/// # use bevy_mod_localization::coverage::folder_coverage;
+/// #
+/// let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/strings/interactive");
+/// let coverage = folder_coverage(folder, &"en-US".parse().unwrap()).unwrap();
+///
+/// assert!(coverage.locales.iter().all(|locale| locale.percentage() == 100.0));
+/// ```
+///
+/// Returns an error if the folder can't be read or contains no file for the reference locale.
+pub fn folder_coverage(
+    folder_path: impl AsRef<Path>,
+    reference_locale: &LanguageIdentifier,
+) -> io::Result<FolderCoverage> {
+    let folder_path = folder_path.as_ref();
+    let mut messages: BTreeMap<LanguageIdentifier, Messages> = BTreeMap::new();
+
+    for file in read_ftl_files(folder_path)? {
+        // Files with invalid names are never loaded
+        let Some(locale) = file.canonical_locale() else {
+            continue;
+        };
+
+        // Count the messages that could be parsed, even if the file contains errors
+        let resource =
+            FluentResource::try_new(file.source.clone()).unwrap_or_else(|(resource, _)| resource);
+
+        messages.insert(locale.clone(), Messages::new(&resource));
+    }
+
+    let reference = messages.get(reference_locale).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("the reference locale `{reference_locale}` is missing"),
+        )
+    })?;
+
+    let locales = messages
+        .iter()
+        .map(|(locale, messages)| messages.coverage(locale, reference))
+        .collect();
+
+    Ok(FolderCoverage {
+        folder_path: folder_path.to_string_lossy().into_owned(),
+        reference_locale: reference_locale.clone(),
+        locales,
+    })
+}
+
+/// Compute the translation coverage of every locale in the folder,
+/// compared to the default fallback locale of the app.
+///
+/// ```
+/// # use bevy_mod_localization::{coverage::default_fallback_coverage, prelude::*};
+/// #
+/// let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/strings/interactive");
+/// let default_fallback = LocaleDefaultFallback::new(Some("en-US"));
+/// let coverage = default_fallback_coverage(folder, &default_fallback).unwrap();
+///
+/// assert_eq!(coverage.reference_locale.to_string(), "en-US");
+/// ```
+///
+/// Returns an error if there is no default fallback locale, see [`folder_coverage`] otherwise.
+pub fn default_fallback_coverage(
+    folder_path: impl AsRef<Path>,
+    default_fallback: &LocaleDefaultFallback,
+) -> io::Result<FolderCoverage> {
+    let reference_locale = default_fallback.locale().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "there is no default fallback locale to compare to",
+        )
+    })?;
+
+    folder_coverage(folder_path, reference_locale)
+}
+
+/// The format of a coverage report.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// A plain text table, e.g. for the terminal.
+    #[default]
+    Table,
+    Json,
+    Markdown,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "json" => Ok(ReportFormat::Json),
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            _ => Err(format!("unknown report format `{s}`")),
+        }
+    }
+}
+
+/// Write a report of the coverage of the folders in the given format.
+///
+/// ```
+/// # use bevy_mod_localization::coverage::{folder_coverage, write_report, ReportFormat};
+/// #
+/// let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/strings/interactive");
+/// let coverage = folder_coverage(folder, &"en-US".parse().unwrap()).unwrap();
+///
+/// let mut report = String::new();
+/// write_report(&mut report, &[coverage], ReportFormat::Markdown).unwrap();
+///
+/// assert!(report.contains("| de | 2/2 | 0/0 | 100.0% |"));
+/// ```
+pub fn write_report(
+    w: &mut impl fmt::Write,
+    coverages: &[FolderCoverage],
+    format: ReportFormat,
+) -> fmt::Result {
+    match format {
+        ReportFormat::Table => write_table(w, coverages),
+        ReportFormat::Json => write_json(w, coverages),
+        ReportFormat::Markdown => write_markdown(w, coverages),
+    }
+}
+
+fn write_table(w: &mut impl fmt::Write, coverages: &[FolderCoverage]) -> fmt::Result {
+    for (index, coverage) in coverages.iter().enumerate() {
+        if index > 0 {
+            writeln!(w)?;
+        }
+
+        writeln!(
+            w,
+            "{} (reference: {})",
+            coverage.folder_path, coverage.reference_locale
+        )?;
+
+        let locale_width = coverage
+            .locales
+            .iter()
+            .map(|locale| locale.locale.to_string().len())
+            .chain([6])
+            .max()
+            .unwrap_or_default();
+
+        writeln!(
+            w,
+            "  {:<locale_width$}  {:>10}  {:>10}  {:>8}",
+            "locale", "messages", "attributes", "coverage"
+        )?;
+
+        for locale in &coverage.locales {
+            writeln!(
+                w,
+                "  {:<locale_width$}  {:>10}  {:>10}  {:>7.1}%",
+                locale.locale.to_string(),
+                format_count(&locale.messages),
+                format_count(&locale.attributes),
+                locale.percentage()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_markdown(w: &mut impl fmt::Write, coverages: &[FolderCoverage]) -> fmt::Result {
+    for (index, coverage) in coverages.iter().enumerate() {
+        if index > 0 {
+            writeln!(w)?;
+        }
+
+        writeln!(w, "## {}", coverage.folder_path)?;
+        writeln!(w)?;
+        writeln!(w, "Reference locale: `{}`", coverage.reference_locale)?;
+        writeln!(w)?;
+        writeln!(w, "| Locale | Messages | Attributes | Coverage |")?;
+        writeln!(w, "| --- | ---: | ---: | ---: |")?;
+
+        for locale in &coverage.locales {
+            writeln!(
+                w,
+                "| {} | {} | {} | {:.1}% |",
+                locale.locale,
+                format_count(&locale.messages),
+                format_count(&locale.attributes),
+                locale.percentage()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_json(w: &mut impl fmt::Write, coverages: &[FolderCoverage]) -> fmt::Result {
+    let json = serde_json::to_string_pretty(coverages).map_err(|_| fmt::Error)?;
+    writeln!(w, "{json}")
+}
+
+fn format_count(count: &CoverageCount) -> String {
+    format!("{}/{}", count.translated, count.total)
+}
+
+/// The messages of a locale, with whether they have a value and the names of their attributes.
+struct Messages(BTreeMap<String, (bool, BTreeSet<String>)>);
+
+impl Messages {
+    fn new(resource: &FluentResource) -> Self {
+        let messages = resource
+            .entries()
+            .filter_map(|entry| match entry {
+                ast::Entry::Message(message) => Some((
+                    message.id.name.to_string(),
+                    (
+                        message.value.is_some(),
+                        message
+                            .attributes
+                            .iter()
+                            .map(|attribute| attribute.id.name.to_string())
+                            .collect(),
+                    ),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        Self(messages)
+    }
+
+    fn coverage(&self, locale: &LanguageIdentifier, reference: &Messages) -> LocaleCoverage {
+        let mut messages = CoverageCount::default();
+        let mut attributes = CoverageCount::default();
+
+        for (id, (has_value, reference_attributes)) in &reference.0 {
+            let translation = self.0.get(id);
+
+            if *has_value {
+                messages.total += 1;
+
+                if translation.is_some_and(|(has_value, _)| *has_value) {
+                    messages.translated += 1;
+                }
+            }
+
+            for attribute in reference_attributes {
+                attributes.total += 1;
+
+                if translation.is_some_and(|(_, attributes)| attributes.contains(attribute)) {
+                    attributes.translated += 1;
+                }
+            }
+        }
+
+        LocaleCoverage {
+            locale: locale.clone(),
+            messages,
+            attributes,
+        }
+    }
+}
@@ -0,0 +1,247 @@
+//! Diagnostics of the localizations, e.g. to log them with the
+//! [`LogDiagnosticsPlugin`](bevy::diagnostic::LogDiagnosticsPlugin).
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics, MAX_DIAGNOSTIC_NAME_WIDTH},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::{
+    localization::{FolderLocalization, Localization, LocalizationFolder, Localizations},
+    plugin::LocalizationSet,
+};
+
+/// Adds diagnostics of the localizations to an app.
+///
+/// The following diagnostics are measured every frame, summed up over all localizations:
+///
+/// - `localization_format_calls`: The number of messages that have been formatted,
+///   not counting messages taken from the format caches.
+/// - `localization_fallback_hits`: The number of messages that have been taken from a fallback
+///   locale, because they are missing in the preceding locales of the resolution chain.
+/// - `localization_format_errors`: The number of messages that had errors in a locale of the
+///   resolution chain, e.g. because of a missing argument. They are not counted as fallback hits,
+///   even if a later locale could format them.
+/// - `localization_format_failures`: The number of messages that couldn't be formatted
+///   in any locale.
+/// - `localization_bundle_build_time`: The time spent building bundles, in milliseconds.
+/// - `localization_format_cache_hits`: The number of messages taken from the format caches.
+/// - `localization_format_cache_misses`: The number of messages that had to be formatted.
+///
+/// The format cache has to be enabled for every localization that should be measured,
+/// see [`Localization::enable_format_cache`].
+///
+/// Additionally, the number of loaded bundles is measured for every folder,
+/// see [`LocalizationDiagnosticsPlugin::bundles_diagnostic_id`].
+/// A typed and a runtime localization of the same folder share the diagnostic,
+/// which is measured once per frame.
+///
+/// ```no_run
+/// # use bevy::{diagnostic::LogDiagnosticsPlugin, prelude::*};
+/// # use bevy_mod_localization::{diagnostics::LocalizationDiagnosticsPlugin, prelude::*};
+/// #
+/// App::new()
+///     .insert_resource(Locale::new("en-US"))
+///     .add_plugins(DefaultPlugins)
+///     .add_plugin(LocalizationPlugin)
+///     .add_plugin(LocalizationDiagnosticsPlugin)
+///     .add_plugin(LogDiagnosticsPlugin::default())
+///     // -- snip --
+///     .run();
+/// ```
+#[derive(Default)]
+pub struct LocalizationDiagnosticsPlugin;
+
+impl Plugin for LocalizationDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LocalizationMeasurements>()
+            .add_startup_system(Self::setup_system)
+            .add_system(
+                Self::diagnostic_system
+                    .in_base_set(CoreSet::Last)
+                    .after(LocalizationSet::Diagnostics),
+            );
+    }
+}
+
+impl LocalizationDiagnosticsPlugin {
+    pub const FORMAT_CALLS: DiagnosticId =
+        DiagnosticId::from_u128(326443573249954376141417140222744590852);
+    pub const FALLBACK_HITS: DiagnosticId =
+        DiagnosticId::from_u128(161526327156702689205386846014159592596);
+    pub const FORMAT_ERRORS: DiagnosticId =
+        DiagnosticId::from_u128(113720459384520164529163310447372885077);
+    pub const FORMAT_FAILURES: DiagnosticId =
+        DiagnosticId::from_u128(155039812198075168663566030737220184809);
+    pub const BUNDLE_BUILD_TIME: DiagnosticId =
+        DiagnosticId::from_u128(19358419223498276942885648075647742970);
+    pub const FORMAT_CACHE_HITS: DiagnosticId =
+        DiagnosticId::from_u128(275969159619801032815699950736037385156);
+    pub const FORMAT_CACHE_MISSES: DiagnosticId =
+        DiagnosticId::from_u128(184343169929777571199222382911606198825);
+
+    /// The ID of the diagnostic measuring the number of loaded bundles of the given folder.
+    ///
+    /// The diagnostic is added once the folder is first measured.
+    pub fn bundles_diagnostic_id(folder_path: &str) -> DiagnosticId {
+        const BUNDLES_NAMESPACE: u128 = 9838900061068659360;
+
+        let mut hasher = DefaultHasher::new();
+        folder_path.hash(&mut hasher);
+
+        DiagnosticId::from_u128((BUNDLES_NAMESPACE << 64) | u128::from(hasher.finish()))
+    }
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::FORMAT_CALLS,
+            "localization_format_calls",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::FALLBACK_HITS,
+            "localization_fallback_hits",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::FORMAT_ERRORS,
+            "localization_format_errors",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::FORMAT_FAILURES,
+            "localization_format_failures",
+            20,
+        ));
+        diagnostics.add(
+            Diagnostic::new(
+                Self::BUNDLE_BUILD_TIME,
+                "localization_bundle_build_time",
+                20,
+            )
+            .with_suffix("ms"),
+        );
+        diagnostics.add(Diagnostic::new(
+            Self::FORMAT_CACHE_HITS,
+            "localization_format_cache_hits",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::FORMAT_CACHE_MISSES,
+            "localization_format_cache_misses",
+            20,
+        ));
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut measurements: ResMut<LocalizationMeasurements>,
+        localizations: Option<Res<Localizations>>,
+    ) {
+        // The typed localizations have already been measured by their own systems
+        for folder in localizations.iter().flat_map(|l| l.folders.values()) {
+            measurements.measure(folder);
+        }
+
+        let measurements = std::mem::take(&mut *measurements);
+
+        diagnostics.add_measurement(Self::FORMAT_CALLS, || measurements.format_calls as f64);
+        diagnostics.add_measurement(Self::FALLBACK_HITS, || measurements.fallback_hits as f64);
+        diagnostics.add_measurement(Self::FORMAT_ERRORS, || measurements.format_errors as f64);
+        diagnostics.add_measurement(Self::FORMAT_FAILURES, || measurements.failures as f64);
+        diagnostics.add_measurement(Self::BUNDLE_BUILD_TIME, || {
+            measurements.build_time.as_secs_f64() * 1000.0
+        });
+        diagnostics.add_measurement(Self::FORMAT_CACHE_HITS, || measurements.cache_hits as f64);
+        diagnostics.add_measurement(Self::FORMAT_CACHE_MISSES, || {
+            measurements.cache_misses as f64
+        });
+
+        for (folder_path, bundles) in measurements.bundles {
+            let id = Self::bundles_diagnostic_id(&folder_path);
+
+            if diagnostics.get(id).is_none() {
+                diagnostics.add(Diagnostic::new(
+                    id,
+                    bundles_diagnostic_name(&folder_path),
+                    20,
+                ));
+            }
+
+            diagnostics.add_measurement(id, || bundles as f64);
+        }
+    }
+}
+
+/// The measurements of the current frame, collected from all localizations.
+#[derive(Debug, Default, Resource)]
+pub struct LocalizationMeasurements {
+    format_calls: u64,
+    fallback_hits: u64,
+    format_errors: u64,
+    failures: u64,
+    build_time: Duration,
+    cache_hits: u64,
+    cache_misses: u64,
+    /// The number of loaded bundles of each folder, by folder path.
+    bundles: HashMap<String, usize>,
+}
+
+impl LocalizationMeasurements {
+    fn measure(&mut self, folder: &FolderLocalization) {
+        let metrics = folder.metrics.take();
+        self.format_calls += metrics.format_calls;
+        self.fallback_hits += metrics.fallback_hits;
+        self.format_errors += metrics.format_errors;
+        self.failures += metrics.failures;
+        self.build_time += metrics.build_time;
+
+        if let Some(cache) = &folder.format_cache {
+            let (hits, misses) = cache.take_measurements();
+            self.cache_hits += hits;
+            self.cache_misses += misses;
+        }
+
+        // A typed and a runtime localization can load the same folder
+        let bundles = self.bundles.entry(folder.folder_path.clone()).or_default();
+        *bundles = (*bundles).max(folder.bundle_map.len());
+    }
+}
+
+/// Collect the measurements of the typed localization.
+pub(crate) fn measure_localization<T: LocalizationFolder>(
+    localization: Res<Localization<T>>,
+    mut measurements: ResMut<LocalizationMeasurements>,
+) {
+    measurements.measure(&localization.folder);
+}
+
+/// Name the bundles diagnostic of a folder, shortening long paths from the start.
+///
+/// Shortened paths get a hash of the full path appended, so that paths ending the same way
+/// still have distinct names.
+fn bundles_diagnostic_name(folder_path: &str) -> String {
+    let prefix = "bundles ";
+    let max_len = MAX_DIAGNOSTIC_NAME_WIDTH - prefix.len();
+    let len = folder_path.chars().count();
+
+    if len <= max_len {
+        return format!("{prefix}{folder_path}");
+    }
+
+    let mut hasher = DefaultHasher::new();
+    folder_path.hash(&mut hasher);
+    let suffix = format!(" {:06x}", hasher.finish() & 0xff_ffff);
+
+    // Leave room for the ellipsis and the hash
+    let tail_len = max_len - 1 - suffix.len();
+    let tail: String = folder_path.chars().skip(len - tail_len).collect();
+    format!("{prefix}…{tail}{suffix}")
+}
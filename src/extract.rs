@@ -0,0 +1,744 @@
+//! Find the message IDs used in Rust source code and compare them with the localization files.
+//!
+//! The source code is scanned for the following calls:
+//!
+//! - `LocalizedText::<T>::new("id")`
+//! - `DynamicLocalizedText::new("folder", "id")`
+//! - The message methods of [`Localization`](crate::localization::Localization),
+//!   e.g. `localization.try_get_message("id")`
+//! - The message methods of [`Localizations`](crate::localization::Localizations),
+//!   e.g. `localizations.try_get_message("folder", "id")`
+//!
+//! Only IDs written as string literals are found.
+//! The localization folder of a call is determined from the type parameter of
+//! `LocalizedText` or from the declaration of the variable that a method is called on,
+//! e.g. `localization: Res<Localization<MenuLocalizationFolder>>`.
+//! The folder types are matched with their `#[folder_path]` attribute of
+//! `#[derive(LocalizationFolder)]` or with the `FOLDER_PATH` of a manual implementation.
+//! The derive is the only macro of the crate; messages are always looked up with
+//! the calls above, so there are no message macros to scan. The arguments of other
+//! macros, e.g. `println!`, are scanned when they are comma-separated expressions.
+//! Files that can't be parsed are reported in [`Extraction::errors`].
+//!
+//! The scan is also available as the `extract` command of the `bevy_mod_localization` binary:
+//!
+//! ```text
+//! bevy_mod_localization extract src --assets assets --reference en-US --write-stubs
+//! ```
+//!
+//! This module requires the `cli` feature.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+};
+
+use fluent::FluentResource;
+use fluent_syntax::ast;
+use syn::{
+    punctuated::Punctuated,
+    visit::{self, Visit},
+};
+use unic_langid::LanguageIdentifier;
+
+use crate::localization::utils::get_ftl_path;
+
+/// A message ID used in the source code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageUsage {
+    pub message_id: String,
+    /// The path of the localization folder, if it could be determined.
+    pub folder_path: Option<String>,
+    /// The source file containing the usage.
+    pub path: PathBuf,
+    /// The line of the usage, starting at 1.
+    pub line: usize,
+}
+
+/// The message IDs and localization folders found in the source code.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Extraction {
+    pub usages: Vec<MessageUsage>,
+    /// The paths of the localization folders defined with a [`LocalizationFolder`](crate::localization::LocalizationFolder) type.
+    pub folder_paths: BTreeSet<String>,
+    /// The source files that couldn't be parsed, so their message IDs are missing.
+    pub errors: Vec<SourceError>,
+}
+
+/// A source file that couldn't be parsed as Rust code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceError {
+    pub path: PathBuf,
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for SourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path.display(), self.line, self.message)
+    }
+}
+
+/// Scan the given Rust sources for message IDs.
+///
+/// ```
+/// # use std::path::PathBuf;
+/// # use bevy_mod_localization::extract::extract_from_sources;
+/// #
+/// let source = r#"
+///     #[derive(LocalizationFolder)]
+///     #[folder_path = "strings/menu"]
+///     struct MenuLocalizationFolder;
+///
+///     fn setup(mut commands: Commands) {
+///         commands.spawn(LocalizedText::<MenuLocalizationFolder>::new("play"));
+///     }
+///
+///     fn print(localization: Res<Localization<MenuLocalizationFolder>>) {
+///         println!("{:?}", localization.try_get_message("quit"));
+///     }
+/// "#;
+///
+/// let extraction = extract_from_sources([(PathBuf::from("src/menu.rs"), source.to_string())]);
+///
+/// let ids: Vec<_> = extraction.usages.iter().map(|usage| usage.message_id.as_str()).collect();
+/// assert_eq!(ids, ["play", "quit"]);
+/// assert!(extraction.usages.iter().all(|usage| usage.folder_path.as_deref() == Some("strings/menu")));
+/// ```
+pub fn extract_from_sources(sources: impl IntoIterator<Item = (PathBuf, String)>) -> Extraction {
+    let mut scan = Scan::default();
+
+    for (path, source) in sources {
+        scan.scan_file(path, &source);
+    }
+
+    scan.finish()
+}
+
+/// Scan all `.rs` files in the given directory and its subdirectories for message IDs.
+///
+/// Hidden directories and `target` directories are skipped.
+pub fn extract_from_dir(dir: impl AsRef<Path>) -> io::Result<Extraction> {
+    let mut sources = Vec::new();
+    collect_sources(dir.as_ref(), &mut sources)?;
+
+    Ok(extract_from_sources(sources))
+}
+
+fn collect_sources(dir: &Path, sources: &mut Vec<(PathBuf, String)>) -> io::Result<()> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    for path in entries {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        if path.is_dir() {
+            if !name.starts_with('.') && name != "target" {
+                collect_sources(&path, sources)?;
+            }
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            let source = fs::read_to_string(&path)?;
+            sources.push((path, source));
+        }
+    }
+
+    Ok(())
+}
+
+/// The comparison of the used message IDs with the reference locale of a folder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderUsageReport {
+    pub folder_path: String,
+    /// The localization file of the reference locale.
+    pub reference_path: PathBuf,
+    /// The IDs used in the source code that are not defined in the reference locale,
+    /// with their first usage.
+    pub missing: Vec<MessageUsage>,
+    /// The messages of the reference locale that are not used in the source code.
+    ///
+    /// Messages whose IDs are built at runtime are reported as unused as well.
+    pub unused: Vec<String>,
+}
+
+impl FolderUsageReport {
+    /// Append a stub for every missing message to the file of the reference locale.
+    ///
+    /// The file is created if it doesn't exist yet.
+    ///
+    /// The stubs use the message ID as value, so they are easy to spot in the app.
+    pub fn write_stubs(&self) -> io::Result<()> {
+        if self.missing.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(folder) = self.reference_path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+
+        // Don't append the first stub to the last line of the file
+        let needs_newline = match fs::read(&self.reference_path) {
+            Ok(source) => source.last().is_some_and(|last| *last != b'\n'),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => false,
+            Err(error) => return Err(error),
+        };
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.reference_path)?;
+
+        if needs_newline {
+            writeln!(file)?;
+        }
+
+        for usage in &self.missing {
+            writeln!(file)?;
+            writeln!(file, "# Used in {}:{}", usage.path.display(), usage.line)?;
+            writeln!(file, "{0} = {0}", usage.message_id)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compare the used message IDs with the reference locale of every localization folder.
+///
+/// The folder paths are resolved relative to the asset folder, e.g. `assets`.
+/// IDs used without a known folder are considered used in every folder.
+pub fn compare_with_folders(
+    extraction: &Extraction,
+    asset_folder: impl AsRef<Path>,
+    reference_locale: &LanguageIdentifier,
+) -> io::Result<Vec<FolderUsageReport>> {
+    let asset_folder = asset_folder.as_ref();
+
+    let mut usages_by_folder: BTreeMap<&str, Vec<&MessageUsage>> = extraction
+        .folder_paths
+        .iter()
+        .map(|folder_path| (folder_path.as_str(), Vec::new()))
+        .collect();
+    let mut unassigned_ids = BTreeSet::new();
+
+    for usage in &extraction.usages {
+        match &usage.folder_path {
+            Some(folder_path) => usages_by_folder.entry(folder_path).or_default().push(usage),
+            None => {
+                unassigned_ids.insert(usage.message_id.as_str());
+            }
+        }
+    }
+
+    let mut reports = Vec::new();
+
+    for (folder_path, usages) in usages_by_folder {
+        let folder = asset_folder.join(folder_path);
+        let reference_path = get_ftl_path(&folder.to_string_lossy(), reference_locale);
+
+        let defined_ids = match fs::read_to_string(&reference_path) {
+            Ok(source) => message_ids(source),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => BTreeSet::new(),
+            Err(error) => return Err(error),
+        };
+
+        let mut missing: Vec<MessageUsage> = Vec::new();
+
+        for usage in usages
+            .iter()
+            .filter(|usage| !defined_ids.contains(&usage.message_id))
+        {
+            if !missing
+                .iter()
+                .any(|missing| missing.message_id == usage.message_id)
+            {
+                missing.push((*usage).clone());
+            }
+        }
+
+        let unused = defined_ids
+            .iter()
+            .filter(|id| {
+                !unassigned_ids.contains(id.as_str())
+                    && !usages.iter().any(|usage| usage.message_id == **id)
+            })
+            .cloned()
+            .collect();
+
+        reports.push(FolderUsageReport {
+            folder_path: folder_path.to_string(),
+            reference_path,
+            missing,
+            unused,
+        });
+    }
+
+    Ok(reports)
+}
+
+/// Get the IDs of all messages in the FTL source, ignoring terms.
+fn message_ids(source: String) -> BTreeSet<String> {
+    let resource = FluentResource::try_new(source).unwrap_or_else(|(resource, _)| resource);
+
+    resource
+        .entries()
+        .filter_map(|entry| match entry {
+            ast::Entry::Message(message) => Some(message.id.name.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The methods of [`Localization`](crate::localization::Localization) taking a message ID,
+/// with the position of the ID argument.
+const MESSAGE_METHODS: [(&str, usize); 7] = [
+    ("try_get_message", 0),
+    ("try_format_message", 0),
+    ("try_get_message_in", 1),
+    ("try_format_message_in", 1),
+    ("format_into", 1),
+    ("write_message", 1),
+    ("message_layer", 0),
+];
+
+/// The methods of [`Localizations`](crate::localization::Localizations) taking a folder path
+/// and a message ID.
+const RUNTIME_MESSAGE_METHODS: [&str; 2] = ["try_get_message", "try_format_message"];
+
+/// The localization folder of a usage, before the folder types are resolved.
+#[derive(Debug)]
+enum FolderRef {
+    Type(String),
+    Path(String),
+    Unknown,
+}
+
+/// The localization resource that a variable or field has been declared as.
+#[derive(Debug, Clone)]
+enum Declared {
+    /// A `Localization<T>`, with the name of `T`.
+    Localization(String),
+    /// The `Localizations` of the runtime folders.
+    Localizations,
+}
+
+#[derive(Default)]
+struct Scan {
+    usages: Vec<(MessageUsage, FolderRef)>,
+    /// The folder paths of the `LocalizationFolder` types.
+    folder_types: BTreeMap<String, String>,
+    errors: Vec<SourceError>,
+}
+
+impl Scan {
+    fn scan_file(&mut self, path: PathBuf, source: &str) {
+        let file = match syn::parse_file(source) {
+            Ok(file) => file,
+            Err(error) => {
+                self.errors.push(SourceError {
+                    line: error.span().start().line,
+                    message: error.to_string(),
+                    path,
+                });
+                return;
+            }
+        };
+
+        let mut visitor = SourceVisitor {
+            scan: self,
+            path: &path,
+            scopes: vec![Vec::new()],
+            fields: Vec::new(),
+        };
+        visitor.visit_file(&file);
+    }
+
+    fn push(&mut self, path: &Path, line: usize, message_id: String, folder: FolderRef) {
+        self.usages.push((
+            MessageUsage {
+                message_id,
+                folder_path: None,
+                path: path.to_path_buf(),
+                line,
+            },
+            folder,
+        ));
+    }
+
+    fn finish(self) -> Extraction {
+        let usages = self
+            .usages
+            .into_iter()
+            .map(|(mut usage, folder)| {
+                usage.folder_path = match folder {
+                    FolderRef::Type(folder_type) => self
+                        .folder_types
+                        .get(&folder_type)
+                        .map(|folder_path| normalize_folder_path(folder_path)),
+                    FolderRef::Path(folder_path) => Some(normalize_folder_path(&folder_path)),
+                    FolderRef::Unknown => None,
+                };
+                usage
+            })
+            .collect();
+
+        Extraction {
+            usages,
+            folder_paths: self
+                .folder_types
+                .values()
+                .map(|folder_path| normalize_folder_path(folder_path))
+                .collect(),
+            errors: self.errors,
+        }
+    }
+}
+
+/// Remove the leading slash, which is optional in folder paths.
+fn normalize_folder_path(folder_path: &str) -> String {
+    folder_path.trim_start_matches('/').to_string()
+}
+
+/// Walks the syntax tree of a source file, keeping track of the declared localizations.
+struct SourceVisitor<'a> {
+    scan: &'a mut Scan,
+    path: &'a Path,
+    /// The variables declared as localizations, by block.
+    scopes: Vec<Vec<(String, Declared)>>,
+    /// The struct fields declared as localizations, e.g. of a `SystemParam`.
+    fields: Vec<(String, Declared)>,
+}
+
+impl SourceVisitor<'_> {
+    fn push(&mut self, line: usize, message_id: String, folder: FolderRef) {
+        self.scan.push(self.path, line, message_id, folder);
+    }
+
+    /// Visit the body of a function or closure with its parameters in scope.
+    fn visit_with_inputs(
+        &mut self,
+        declarations: impl IntoIterator<Item = (String, Declared)>,
+        visit: impl FnOnce(&mut Self),
+    ) {
+        self.scopes.push(declarations.into_iter().collect());
+        visit(self);
+        self.scopes.pop();
+    }
+
+    /// Determine what the receiver of a method call has been declared as.
+    fn resolve_receiver(&self, receiver: &syn::Expr) -> Option<&Declared> {
+        match receiver {
+            syn::Expr::Path(path) => {
+                let name = path.path.get_ident()?.to_string();
+
+                self.scopes
+                    .iter()
+                    .rev()
+                    .flat_map(|scope| scope.iter().rev())
+                    .find(|(declared, _)| *declared == name)
+                    .map(|(_, declared)| declared)
+            }
+            syn::Expr::Field(field) => {
+                let syn::Member::Named(name) = &field.member else {
+                    return None;
+                };
+
+                self.fields
+                    .iter()
+                    .find(|(declared, _)| name == declared)
+                    .map(|(_, declared)| declared)
+            }
+            syn::Expr::Reference(reference) => self.resolve_receiver(&reference.expr),
+            syn::Expr::Paren(paren) => self.resolve_receiver(&paren.expr),
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Deref(_),
+                expr,
+                ..
+            }) => self.resolve_receiver(expr),
+            _ => None,
+        }
+    }
+
+    fn scan_method_call(&mut self, call: &syn::ExprMethodCall) {
+        let method = call.method.to_string();
+        let line = call.method.span().start().line;
+        let string_arg = |position: usize| call.args.iter().nth(position).and_then(string_literal);
+
+        let declared = self.resolve_receiver(&call.receiver).cloned();
+
+        // `Localizations` take the folder path before the message ID
+        let is_runtime = match &declared {
+            Some(Declared::Localizations) => true,
+            Some(Declared::Localization(_)) => false,
+            None => string_arg(0).is_some() && string_arg(1).is_some(),
+        };
+
+        if is_runtime {
+            if !RUNTIME_MESSAGE_METHODS.contains(&method.as_str()) {
+                return;
+            }
+
+            if let Some(id) = string_arg(1) {
+                let folder = string_arg(0).map_or(FolderRef::Unknown, FolderRef::Path);
+                self.push(line, id, folder);
+            }
+
+            return;
+        }
+
+        let Some((_, id_position)) = MESSAGE_METHODS.iter().find(|(name, _)| *name == method)
+        else {
+            return;
+        };
+
+        if let Some(id) = string_arg(*id_position) {
+            let folder = match declared {
+                Some(Declared::Localization(folder_type)) => FolderRef::Type(folder_type),
+                _ => FolderRef::Unknown,
+            };
+            self.push(line, id, folder);
+        }
+    }
+
+    /// Scan `LocalizedText::<T>::new("id")` and `DynamicLocalizedText::new("folder", "id")`.
+    fn scan_call(&mut self, call: &syn::ExprCall) {
+        let syn::Expr::Path(path) = &*call.func else {
+            return;
+        };
+
+        let segments: Vec<&syn::PathSegment> = path.path.segments.iter().collect();
+        let [.., component, new] = segments.as_slice() else {
+            return;
+        };
+
+        if new.ident != "new" {
+            return;
+        }
+
+        let line = component.ident.span().start().line;
+        let string_arg = |position: usize| call.args.iter().nth(position).and_then(string_literal);
+
+        if component.ident == "LocalizedText" {
+            if let Some(id) = string_arg(0) {
+                let folder = first_type_argument(&component.arguments)
+                    .and_then(type_name)
+                    .map_or(FolderRef::Unknown, FolderRef::Type);
+                self.push(line, id, folder);
+            }
+        } else if component.ident == "DynamicLocalizedText" {
+            if let (Some(folder_path), Some(id)) = (string_arg(0), string_arg(1)) {
+                self.push(line, id, FolderRef::Path(folder_path));
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for SourceVisitor<'_> {
+    fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+        self.visit_with_inputs(fn_inputs(&item.sig), |visitor| {
+            visit::visit_item_fn(visitor, item)
+        });
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast syn::ImplItemFn) {
+        self.visit_with_inputs(fn_inputs(&item.sig), |visitor| {
+            visit::visit_impl_item_fn(visitor, item)
+        });
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast syn::TraitItemFn) {
+        self.visit_with_inputs(fn_inputs(&item.sig), |visitor| {
+            visit::visit_trait_item_fn(visitor, item)
+        });
+    }
+
+    fn visit_expr_closure(&mut self, closure: &'ast syn::ExprClosure) {
+        let declarations = closure.inputs.iter().filter_map(declaration);
+        self.visit_with_inputs(declarations, |visitor| {
+            visit::visit_expr_closure(visitor, closure)
+        });
+    }
+
+    fn visit_block(&mut self, block: &'ast syn::Block) {
+        self.scopes.push(Vec::new());
+        visit::visit_block(self, block);
+        self.scopes.pop();
+    }
+
+    fn visit_local(&mut self, local: &'ast syn::Local) {
+        visit::visit_local(self, local);
+
+        // The variable is only in scope after its declaration
+        if let (Some(declaration), Some(scope)) = (declaration(&local.pat), self.scopes.last_mut())
+        {
+            scope.push(declaration);
+        }
+    }
+
+    fn visit_item_struct(&mut self, item: &'ast syn::ItemStruct) {
+        if let Some(folder_path) = folder_path_attribute(&item.attrs) {
+            self.scan
+                .folder_types
+                .insert(item.ident.to_string(), folder_path);
+        }
+
+        for field in &item.fields {
+            if let (Some(name), Some(declared)) = (&field.ident, declared_localization(&field.ty)) {
+                self.fields.push((name.to_string(), declared));
+            }
+        }
+
+        visit::visit_item_struct(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        let is_folder = item.trait_.as_ref().is_some_and(|(_, path, _)| {
+            path.segments
+                .last()
+                .is_some_and(|segment| segment.ident == "LocalizationFolder")
+        });
+
+        if let (true, Some(name)) = (is_folder, type_name(&item.self_ty)) {
+            let folder_path = item.items.iter().find_map(|item| match item {
+                syn::ImplItem::Const(constant) if constant.ident == "FOLDER_PATH" => {
+                    string_literal(&constant.expr)
+                }
+                _ => None,
+            });
+
+            if let Some(folder_path) = folder_path {
+                self.scan.folder_types.insert(name, folder_path);
+            }
+        }
+
+        visit::visit_item_impl(self, item);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        self.scan_method_call(call);
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        self.scan_call(call);
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        // Most macros taking expressions, e.g. `println!` or `vec!`, separate them with commas
+        let parser = Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated;
+
+        if let Ok(expressions) = mac.parse_body_with(parser) {
+            for expression in &expressions {
+                self.visit_expr(expression);
+            }
+        }
+    }
+}
+
+/// The parameters of a function declared as localizations.
+fn fn_inputs(signature: &syn::Signature) -> impl Iterator<Item = (String, Declared)> + '_ {
+    signature.inputs.iter().filter_map(|input| match input {
+        syn::FnArg::Typed(input) => typed_declaration(input),
+        syn::FnArg::Receiver(_) => None,
+    })
+}
+
+/// Get the variable declared as localization by a pattern like `menu: Res<Localization<T>>`.
+fn declaration(pat: &syn::Pat) -> Option<(String, Declared)> {
+    match pat {
+        syn::Pat::Type(pat_type) => typed_declaration(pat_type),
+        _ => None,
+    }
+}
+
+fn typed_declaration(pat_type: &syn::PatType) -> Option<(String, Declared)> {
+    let syn::Pat::Ident(ident) = &*pat_type.pat else {
+        return None;
+    };
+
+    Some((
+        ident.ident.to_string(),
+        declared_localization(&pat_type.ty)?,
+    ))
+}
+
+/// Find `Localization<T>` or `Localizations` in a type like `Res<Localization<T>>`.
+fn declared_localization(ty: &syn::Type) -> Option<Declared> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.iter().find_map(|segment| {
+            if segment.ident == "Localization" {
+                return first_type_argument(&segment.arguments)
+                    .and_then(type_name)
+                    .map(Declared::Localization);
+            }
+
+            if segment.ident == "Localizations" {
+                return Some(Declared::Localizations);
+            }
+
+            let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+                return None;
+            };
+
+            arguments.args.iter().find_map(|argument| match argument {
+                syn::GenericArgument::Type(ty) => declared_localization(ty),
+                _ => None,
+            })
+        }),
+        syn::Type::Reference(reference) => declared_localization(&reference.elem),
+        syn::Type::Paren(paren) => declared_localization(&paren.elem),
+        syn::Type::Group(group) => declared_localization(&group.elem),
+        _ => None,
+    }
+}
+
+fn first_type_argument(arguments: &syn::PathArguments) -> Option<&syn::Type> {
+    let syn::PathArguments::AngleBracketed(arguments) = arguments else {
+        return None;
+    };
+
+    arguments.args.iter().find_map(|argument| match argument {
+        syn::GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// The last segment of a type path, e.g. `MenuFolder` for `crate::menu::MenuFolder`.
+fn type_name(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Get the value of a `#[folder_path = "..."]` attribute.
+fn folder_path_attribute(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs.iter().find_map(|attr| match &attr.meta {
+        syn::Meta::NameValue(meta) if meta.path.is_ident("folder_path") => {
+            string_literal(&meta.value)
+        }
+        _ => None,
+    })
+}
+
+/// Get the value of a string literal, with its escapes resolved.
+fn string_literal(expr: &syn::Expr) -> Option<String> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Some(lit.value()),
+        _ => None,
+    }
+}
@@ -1,5 +1,9 @@
+use std::sync::Arc;
+
 pub use fluent::FluentResource;
 use intl_memoizer::concurrent::IntlLangMemoizer;
 
 /// Concurrent version of the FluentBundle
-pub type FluentBundle = fluent::bundle::FluentBundle<FluentResource, IntlLangMemoizer>;
+///
+/// The resources are shared, so that the bundle can be rebuilt without parsing them again.
+pub type FluentBundle = fluent::bundle::FluentBundle<Arc<FluentResource>, IntlLangMemoizer>;
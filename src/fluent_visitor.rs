@@ -0,0 +1,134 @@
+//! Walk the syntax tree of Fluent messages and terms.
+
+use std::borrow::Cow;
+
+use fluent_syntax::{ast, unicode::unescape_unicode_to_string};
+
+/// The parts of a pattern that a visitor is interested in.
+///
+/// All methods do nothing by default, so a visitor only implements the ones it needs.
+pub(crate) trait PatternVisitor<'s> {
+    /// Called for the text that is displayed, i.e. the text elements and the literals
+    /// placed directly in the pattern, with their escapes resolved.
+    fn visit_text(&mut self, _text: Cow<'s, str>) {}
+
+    /// Called for every inline expression, including the selectors and call arguments.
+    fn visit_inline_expression(&mut self, _expression: &ast::InlineExpression<&'s str>) {}
+
+    /// Called for every selection, before its selector and variants are walked.
+    fn visit_select(
+        &mut self,
+        _selector: &ast::InlineExpression<&'s str>,
+        _variants: &[ast::Variant<&'s str>],
+    ) {
+    }
+}
+
+/// The value and attributes of a message or term.
+pub(crate) type EntryPatterns<'a, 's> = (
+    Option<&'a ast::Pattern<&'s str>>,
+    &'a [ast::Attribute<&'s str>],
+);
+
+/// Get the value and attributes of a message or term, or `None` for comments and junk.
+pub(crate) fn entry_patterns<'a, 's>(
+    entry: &'a ast::Entry<&'s str>,
+) -> Option<EntryPatterns<'a, 's>> {
+    match entry {
+        ast::Entry::Message(message) => Some((message.value.as_ref(), &message.attributes)),
+        ast::Entry::Term(term) => Some((Some(&term.value), &term.attributes)),
+        _ => None,
+    }
+}
+
+/// Walk the value and attributes of a message or term. Comments and junk are skipped.
+pub(crate) fn walk_entry<'s>(entry: &ast::Entry<&'s str>, visitor: &mut impl PatternVisitor<'s>) {
+    let Some((value, attributes)) = entry_patterns(entry) else {
+        return;
+    };
+
+    for pattern in value
+        .into_iter()
+        .chain(attributes.iter().map(|attribute| &attribute.value))
+    {
+        walk_pattern(pattern, visitor);
+    }
+}
+
+pub(crate) fn walk_pattern<'s>(
+    pattern: &ast::Pattern<&'s str>,
+    visitor: &mut impl PatternVisitor<'s>,
+) {
+    for element in &pattern.elements {
+        match element {
+            ast::PatternElement::TextElement { value } => visitor.visit_text(Cow::Borrowed(*value)),
+            ast::PatternElement::Placeable { expression } => {
+                walk_expression(expression, true, visitor)
+            }
+        }
+    }
+}
+
+/// Walk an expression. Only the literals of displayed expressions are visited as text.
+fn walk_expression<'s>(
+    expression: &ast::Expression<&'s str>,
+    displayed: bool,
+    visitor: &mut impl PatternVisitor<'s>,
+) {
+    match expression {
+        ast::Expression::Select { selector, variants } => {
+            visitor.visit_select(selector, variants);
+            walk_inline_expression(selector, false, visitor);
+
+            for variant in variants {
+                walk_pattern(&variant.value, visitor);
+            }
+        }
+        ast::Expression::Inline(inline) => walk_inline_expression(inline, displayed, visitor),
+    }
+}
+
+fn walk_inline_expression<'s>(
+    expression: &ast::InlineExpression<&'s str>,
+    displayed: bool,
+    visitor: &mut impl PatternVisitor<'s>,
+) {
+    visitor.visit_inline_expression(expression);
+
+    match expression {
+        ast::InlineExpression::StringLiteral { value } if displayed => {
+            visitor.visit_text(unescape_unicode_to_string(value))
+        }
+        ast::InlineExpression::NumberLiteral { value } if displayed => {
+            visitor.visit_text(Cow::Borrowed(*value))
+        }
+        ast::InlineExpression::Placeable { expression } => {
+            walk_expression(expression, displayed, visitor)
+        }
+        ast::InlineExpression::FunctionReference { arguments, .. } => {
+            walk_arguments(arguments, visitor)
+        }
+        ast::InlineExpression::TermReference {
+            arguments: Some(arguments),
+            ..
+        } => walk_arguments(arguments, visitor),
+        ast::InlineExpression::StringLiteral { .. }
+        | ast::InlineExpression::NumberLiteral { .. }
+        | ast::InlineExpression::MessageReference { .. }
+        | ast::InlineExpression::TermReference { .. }
+        | ast::InlineExpression::VariableReference { .. } => {}
+    }
+}
+
+fn walk_arguments<'s>(
+    arguments: &ast::CallArguments<&'s str>,
+    visitor: &mut impl PatternVisitor<'s>,
+) {
+    for expression in arguments
+        .positional
+        .iter()
+        .chain(arguments.named.iter().map(|argument| &argument.value))
+    {
+        walk_inline_expression(expression, false, visitor);
+    }
+}
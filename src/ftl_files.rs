@@ -0,0 +1,127 @@
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use intl_pluralrules::{PluralCategory, PluralRuleType, PluralRules};
+use unic_langid::LanguageIdentifier;
+
+/// A localization file read from a folder on disk.
+pub(crate) struct FtlFile {
+    pub(crate) path: PathBuf,
+    /// The locale named by the file, if the file name is a valid language tag.
+    pub(crate) locale: Option<LanguageIdentifier>,
+    pub(crate) source: String,
+}
+
+impl FtlFile {
+    /// The locale of the file, if the file name is a language tag in canonical form.
+    ///
+    /// Only these files are loaded, see [`get_ftl_path`](crate::localization::utils::get_ftl_path).
+    pub(crate) fn canonical_locale(&self) -> Option<&LanguageIdentifier> {
+        self.locale
+            .as_ref()
+            .filter(|locale| **locale == self.stem())
+    }
+
+    /// The name of the file without the `.ftl` extension.
+    pub(crate) fn stem(&self) -> &str {
+        self.path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+    }
+}
+
+/// Read all `.ftl` files of the localization folder, sorted by their path.
+pub(crate) fn read_ftl_files(folder_path: &Path) -> io::Result<Vec<FtlFile>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(folder_path)? {
+        let path = entry?.path();
+
+        if !path.is_file()
+            || path
+                .extension()
+                .map_or(true, |extension| extension != "ftl")
+        {
+            continue;
+        }
+
+        let source = fs::read_to_string(&path)?;
+        let locale = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok());
+
+        files.push(FtlFile {
+            path,
+            locale,
+            source,
+        });
+    }
+
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Get the line number of the given byte position, starting at 1.
+pub(crate) fn line_of(source: &str, position: usize) -> usize {
+    let position = position.min(source.len());
+    source.as_bytes()[..position]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+/// Find the line numbers that the entry with the given ID is defined on, starting at 1.
+///
+/// Terms are prefixed with a `-`, just like in the FTL syntax.
+pub(crate) fn entry_lines(source: &str, id: &str) -> Vec<usize> {
+    source
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            line.strip_prefix(id)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map(|(index, _)| index + 1)
+        .collect()
+}
+
+/// The names of the plural categories, as used for the variant keys.
+pub(crate) const PLURAL_CATEGORY_NAMES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// Determine the cardinal plural categories that the locale uses.
+///
+/// The plural rules only map numbers to categories, so the categories are found by sampling.
+pub(crate) fn plural_categories(locale: &LanguageIdentifier) -> Option<BTreeSet<&'static str>> {
+    let language = LanguageIdentifier::from_parts(locale.language, None, None, &[]);
+    let rules = PluralRules::create(locale.clone(), PluralRuleType::CARDINAL)
+        .or_else(|_| PluralRules::create(language, PluralRuleType::CARDINAL))
+        .ok()?;
+
+    let integers = (0..=1000)
+        .chain([10_000, 100_000, 1_000_000])
+        .map(|n| rules.select(n));
+    let decimals = ["0.0", "0.1", "0.5", "1.0", "1.1", "1.5", "2.5", "10.1"]
+        .into_iter()
+        .map(|n| rules.select(n));
+
+    let categories = integers
+        .chain(decimals)
+        .filter_map(Result::ok)
+        .map(|category| match category {
+            PluralCategory::ZERO => "zero",
+            PluralCategory::ONE => "one",
+            PluralCategory::TWO => "two",
+            PluralCategory::FEW => "few",
+            PluralCategory::MANY => "many",
+            PluralCategory::OTHER => "other",
+        })
+        .collect();
+
+    Some(categories)
+}
@@ -0,0 +1,1116 @@
+//! Convert localization folders to gettext PO files and back, e.g. for translation vendors.
+//!
+//! The reference locale is exported as a `.pot` template and every other locale as a `.po` file.
+//! Each message value, attribute and term becomes an entry with the `msgctxt` set to its ID,
+//! e.g. `greeting`, `greeting.title` or `-brand`, and the `msgid` set to its Fluent source in
+//! the reference locale. Comments are exported as notes for the translators.
+//! Comments that don't belong to an entry, i.e. standalone, group (`##`) and resource (`###`)
+//! comments, are exported as notes of the following entry, or of the header if no entry follows.
+//!
+//! Messages that only consist of a plural selection, e.g. `{ $count -> [one] ... *[other] ... }`,
+//! are exported as plural entries and imported as selection again.
+//! The plural categories of the locale without a gettext form of their own, e.g. `other` for
+//! the fractions in Russian and Polish, are imported with the text of the last form.
+//! Other selections are exported with their full Fluent source, as are all selections of the
+//! locales without a known `Plural-Forms` expression, e.g. Irish or Lithuanian.
+//!
+//! The conversion is also available as the `po-export` and `po-import` commands of the
+//! `bevy_mod_localization` binary:
+//!
+//! ```text
+//! bevy_mod_localization po-export assets/strings/menu --po po --reference en-US
+//! bevy_mod_localization po-import assets/strings/menu --po po
+//! ```
+//!
+//! This module requires the `cli` feature.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use fluent_syntax::{ast, parser, serializer};
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    ftl_files::{plural_categories, read_ftl_files, PLURAL_CATEGORY_NAMES},
+    localization::utils::get_ftl_path,
+};
+
+/// The translator note storing the selector of a plural entry, needed for the import.
+const PLURAL_SELECTOR_NOTE: &str = "Plural selector: ";
+
+/// The FTL prefixes of the comments that don't belong to an entry, with the notes storing them.
+const COMMENT_NOTES: [(&str, &str); 3] = [
+    ("#", "Comment: "),
+    ("##", "Group comment: "),
+    ("###", "Resource comment: "),
+];
+
+/// An error in a PO file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoError {
+    /// The line of the entry containing the error, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for PoError {}
+
+/// Export the FTL source of the reference locale to a PO template.
+///
+/// ```
+/// # use bevy_mod_localization::gettext::ftl_to_pot;
+/// #
+/// let pot = ftl_to_pot("# The title of the main menu\ntitle = My Game\n");
+///
+/// assert!(pot.contains("#. The title of the main menu\nmsgctxt \"title\"\nmsgid \"My Game\"\nmsgstr \"\"\n"));
+/// ```
+pub fn ftl_to_pot(reference_source: &str) -> String {
+    export(reference_source, None)
+}
+
+/// Export the FTL source of a locale to a PO file, using the reference locale for the `msgid`s.
+///
+/// Entries that are not translated have an empty `msgstr`.
+///
+/// ```
+/// # use bevy_mod_localization::gettext::ftl_to_po;
+/// #
+/// let reference = "items = { $count ->\n    [one] One item\n   *[other] { $count } items\n}\n";
+/// let translation = "items = { $count ->\n    [one] Ein Gegenstand\n   *[other] { $count } Gegenstände\n}\n";
+///
+/// let po = ftl_to_po(reference, translation, &"de".parse().unwrap());
+///
+/// assert!(po.contains("\"Plural-Forms: nplurals=2; plural=(n != 1);\\n\""));
+/// assert!(po.contains(
+///     "#. Plural selector: $count
+/// msgctxt \"items\"
+/// msgid \"One item\"
+/// msgid_plural \"{ $count } items\"
+/// msgstr[0] \"Ein Gegenstand\"
+/// msgstr[1] \"{ $count } Gegenstände\"
+/// "
+/// ));
+/// ```
+pub fn ftl_to_po(
+    reference_source: &str,
+    translation_source: &str,
+    locale: &LanguageIdentifier,
+) -> String {
+    export(reference_source, Some((translation_source, locale)))
+}
+
+/// Import a translated PO file as FTL source.
+///
+/// Untranslated and fuzzy entries are skipped, so the fallback locales are used for them.
+///
+/// ```
+/// # use bevy_mod_localization::gettext::{ftl_to_po, po_to_ftl};
+/// #
+/// let source = "# The title of the main menu\ntitle = Mein Spiel\n    .tooltip = Hauptmenü\n";
+/// let po = ftl_to_po(source, source, &"de".parse().unwrap());
+///
+/// assert_eq!(po_to_ftl(&po, &"de".parse().unwrap()).unwrap(), source);
+/// ```
+pub fn po_to_ftl(po_source: &str, locale: &LanguageIdentifier) -> Result<String, PoError> {
+    let forms = plural_forms(locale);
+    let mut entries: Vec<FtlEntry> = Vec::new();
+    let mut entry_indices: HashMap<String, usize> = HashMap::new();
+    // The entry comments are exported with the first unit, which might not be translated
+    let mut entry_comments: HashMap<String, Vec<String>> = HashMap::new();
+    // The comments that don't belong to an entry, until the next imported entry
+    let mut pending_comments: Vec<FtlComment> = Vec::new();
+    let mut trailing_comments: Vec<FtlComment> = Vec::new();
+
+    for po_entry in parse_po(po_source)? {
+        let Some(context) = &po_entry.context else {
+            // The header has no context, its notes are the comments at the end of the file
+            if po_entry.id.is_empty() {
+                trailing_comments.extend(FtlComment::from_notes(&po_entry.notes));
+                continue;
+            }
+
+            return Err(po_entry.error("the entry has no `msgctxt` with the Fluent ID"));
+        };
+
+        let (id, attribute) = match context.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (context.as_str(), None),
+        };
+
+        pending_comments.extend(FtlComment::from_notes(&po_entry.notes));
+        entry_comments
+            .entry(id.to_string())
+            .or_insert_with(|| po_entry.entry_comment());
+
+        if po_entry.flags.iter().any(|flag| flag == "fuzzy") {
+            continue;
+        }
+
+        let Some(value) = po_entry.ftl_value(forms.as_ref())? else {
+            continue;
+        };
+
+        let index = *entry_indices.entry(id.to_string()).or_insert_with(|| {
+            entries.push(FtlEntry {
+                id: id.to_string(),
+                line: po_entry.line,
+                comments: std::mem::take(&mut pending_comments),
+                comment: entry_comments.get(id).cloned().unwrap_or_default(),
+                value: None,
+                attributes: Vec::new(),
+            });
+            entries.len() - 1
+        });
+
+        match attribute {
+            Some(attribute) => entries[index]
+                .attributes
+                .push((attribute.to_string(), value)),
+            None => entries[index].value = Some(value),
+        }
+    }
+
+    let mut ftl = String::new();
+    // A standalone comment would belong to an entry directly after it
+    let mut after_comment = false;
+
+    for entry in &entries {
+        for comment in &entry.comments {
+            if !ftl.is_empty() {
+                ftl.push('\n');
+            }
+
+            ftl.push_str(&comment.to_source());
+            after_comment = true;
+        }
+
+        let source = entry.to_source();
+
+        if let Err((_, errors)) = parser::parse(source.as_str()) {
+            let error = errors.first().map(ToString::to_string).unwrap_or_default();
+
+            return Err(PoError {
+                line: entry.line,
+                message: format!("invalid Fluent syntax in `{}`: {error}", entry.id),
+            });
+        }
+
+        if !ftl.is_empty() && (after_comment || !entry.comment.is_empty()) {
+            ftl.push('\n');
+        }
+
+        ftl.push_str(&source);
+        after_comment = false;
+    }
+
+    for comment in pending_comments.iter().chain(&trailing_comments) {
+        if !ftl.is_empty() {
+            ftl.push('\n');
+        }
+
+        ftl.push_str(&comment.to_source());
+    }
+
+    Ok(ftl)
+}
+
+/// Export the localization folder to `<folder name>.pot` and `<locale>.po` files in the PO folder.
+///
+/// Returns the paths of the written files, or an error if there is no file for the reference locale.
+pub fn export_folder(
+    folder_path: impl AsRef<Path>,
+    reference_locale: &LanguageIdentifier,
+    po_folder: impl AsRef<Path>,
+) -> io::Result<Vec<PathBuf>> {
+    let folder_path = folder_path.as_ref();
+    let po_folder = po_folder.as_ref();
+
+    let files = read_ftl_files(folder_path)?;
+    let reference = files
+        .iter()
+        .find(|file| file.canonical_locale() == Some(reference_locale))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("the reference locale `{reference_locale}` is missing"),
+            )
+        })?;
+
+    fs::create_dir_all(po_folder)?;
+
+    let folder_name = folder_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "messages".to_string());
+    let template_path = po_folder.join(format!("{folder_name}.pot"));
+    fs::write(&template_path, ftl_to_pot(&reference.source))?;
+
+    let mut paths = vec![template_path];
+
+    for file in &files {
+        let Some(locale) = file.canonical_locale() else {
+            continue;
+        };
+
+        if locale == reference_locale {
+            continue;
+        }
+
+        let path = po_folder.join(format!("{locale}.po"));
+        fs::write(&path, ftl_to_po(&reference.source, &file.source, locale))?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Import the `<locale>.po` files in the PO folder into the localization folder.
+///
+/// The FTL files of the imported locales are replaced. Returns the paths of the written files.
+pub fn import_folder(
+    po_folder: impl AsRef<Path>,
+    folder_path: impl AsRef<Path>,
+) -> io::Result<Vec<PathBuf>> {
+    let mut po_paths = fs::read_dir(po_folder)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .filter(|path| {
+            path.as_ref().map_or(true, |path| {
+                path.extension().is_some_and(|extension| extension == "po")
+            })
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    po_paths.sort();
+
+    let mut paths = Vec::new();
+
+    for po_path in po_paths {
+        let invalid_data = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {message}", po_path.display()),
+            )
+        };
+
+        let locale: LanguageIdentifier = po_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse().ok())
+            .ok_or_else(|| invalid_data("the file name is not a locale".to_string()))?;
+
+        let ftl = po_to_ftl(&fs::read_to_string(&po_path)?, &locale)
+            .map_err(|error| invalid_data(error.to_string()))?;
+
+        let path = get_ftl_path(&folder_path.as_ref().to_string_lossy(), &locale);
+        fs::write(&path, ftl)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// How gettext selects the plural forms of a locale.
+struct PluralForms {
+    /// The `Plural-Forms` expression.
+    formula: &'static str,
+    /// The plural category of every form.
+    categories: Vec<&'static str>,
+    /// The plural categories of the locale without a form of their own, which use the last form.
+    extra_categories: Vec<&'static str>,
+}
+
+/// The common `Plural-Forms` of gettext, with the plural categories of their forms.
+///
+/// Only the integers are covered by the forms, so the categories used for fractions are missing,
+/// e.g. `other` in Russian or `many` in Czech.
+const KNOWN_PLURAL_FORMS: [(&[&str], &str, &[&str]); 7] = [
+    (
+        &[
+            "bg", "ca", "da", "de", "el", "en", "eo", "es", "et", "fi", "hu", "it", "nb", "nl",
+            "nn", "no", "sv", "tr",
+        ],
+        "(n != 1)",
+        &["one", "other"],
+    ),
+    (&["fr", "pt"], "(n > 1)", &["one", "other"]),
+    (&["id", "ja", "ko", "ms", "th", "vi", "zh"], "0", &["other"]),
+    (
+        &["be", "ru", "uk"],
+        "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        &["one", "few", "many"],
+    ),
+    (
+        &["pl"],
+        "(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<10 || n%100>=20) ? 1 : 2)",
+        &["one", "few", "many"],
+    ),
+    (
+        &["cs", "sk"],
+        "(n==1) ? 0 : (n>=2 && n<=4) ? 1 : 2",
+        &["one", "few", "other"],
+    ),
+    (
+        &["ar"],
+        "(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5)",
+        &["zero", "one", "two", "few", "many", "other"],
+    ),
+];
+
+/// Get the plural forms of the locale, or `None` without a known `Plural-Forms` expression.
+///
+/// Without the expression, gettext tools can't tell which form a number selects, so the
+/// plural entries of these locales couldn't be translated.
+fn plural_forms(locale: &LanguageIdentifier) -> Option<PluralForms> {
+    let language = locale.language.as_str();
+
+    let (_, formula, categories) = KNOWN_PLURAL_FORMS
+        .iter()
+        .find(|(languages, _, _)| languages.contains(&language))?;
+
+    let locale_categories = plural_categories(locale);
+
+    // Every selection needs an `other` variant as default
+    let extra_categories = PLURAL_CATEGORY_NAMES
+        .into_iter()
+        .filter(|category| {
+            locale_categories
+                .as_ref()
+                .map_or(*category == "other", |locale_categories| {
+                    locale_categories.contains(category)
+                })
+        })
+        .filter(|category| !categories.contains(category))
+        .collect();
+
+    Some(PluralForms {
+        formula,
+        categories: categories.to_vec(),
+        extra_categories,
+    })
+}
+
+fn export(reference_source: &str, translation: Option<(&str, &LanguageIdentifier)>) -> String {
+    let reference = parse_ftl(reference_source);
+    let translation_resource = translation.map(|(source, _)| parse_ftl(source));
+    let translations: HashMap<String, &ast::Pattern<&str>> = translation_resource
+        .iter()
+        .flat_map(|resource| units(resource).units)
+        .map(|unit| (unit.context, unit.pattern))
+        .collect();
+
+    let locale = translation.map(|(_, locale)| locale);
+    let forms = locale.and_then(plural_forms);
+    let Units {
+        units,
+        trailing_notes,
+    } = units(&reference);
+
+    let mut po = String::new();
+    write_header(&mut po, locale, forms.as_ref(), &trailing_notes);
+
+    for unit in units {
+        let translated = translations.get(&unit.context).copied();
+        let mut notes = unit.notes;
+
+        let plural = as_plural(unit.pattern).filter(|_| locale.is_none() || forms.is_some());
+
+        let entry = match plural {
+            Some(plural) => {
+                notes.push(format!(
+                    "{PLURAL_SELECTOR_NOTE}{}",
+                    inline_source(plural.selector)
+                ));
+
+                let strings = match &forms {
+                    Some(forms) => forms
+                        .categories
+                        .iter()
+                        .map(|category| {
+                            translated
+                                .map(|pattern| match as_plural(pattern) {
+                                    Some(plural) => plural.variant_source(category),
+                                    None => pattern_source(pattern),
+                                })
+                                .unwrap_or_default()
+                        })
+                        .collect(),
+                    // Templates have two empty forms
+                    None => vec![String::new(), String::new()],
+                };
+
+                PoEntry {
+                    notes,
+                    context: unit.context,
+                    id: plural.variant_source("one"),
+                    id_plural: Some(plural.variant_source("other")),
+                    strings,
+                }
+            }
+            None => PoEntry {
+                notes,
+                context: unit.context,
+                id: pattern_source(unit.pattern),
+                id_plural: None,
+                strings: vec![translated.map(pattern_source).unwrap_or_default()],
+            },
+        };
+
+        po.push('\n');
+        entry.write(&mut po);
+    }
+
+    po
+}
+
+fn write_header(
+    po: &mut String,
+    locale: Option<&LanguageIdentifier>,
+    forms: Option<&PluralForms>,
+    notes: &[String],
+) {
+    let mut header = "MIME-Version: 1.0\n\
+        Content-Type: text/plain; charset=UTF-8\n\
+        Content-Transfer-Encoding: 8bit\n"
+        .to_string();
+
+    if let Some(locale) = locale {
+        let _ = writeln!(header, "Language: {locale}");
+    }
+
+    if let Some(PluralForms {
+        formula,
+        categories,
+        ..
+    }) = forms
+    {
+        let _ = writeln!(
+            header,
+            "Plural-Forms: nplurals={}; plural={formula};",
+            categories.len()
+        );
+    }
+
+    for note in notes {
+        let _ = writeln!(po, "#. {note}");
+    }
+
+    write_po_string(po, "msgid", "");
+    write_po_string(po, "msgstr", &header);
+}
+
+fn parse_ftl(source: &str) -> ast::Resource<&str> {
+    // Export the entries that could be parsed, even if the file contains errors
+    parser::parse(source).unwrap_or_else(|(resource, _)| resource)
+}
+
+/// A translatable pattern of an FTL file, i.e. a message or term value or an attribute.
+struct Unit<'a, 's> {
+    /// The ID of the entry, followed by the attribute name, e.g. `greeting.title`.
+    context: String,
+    /// The notes for the comments before the pattern.
+    ///
+    /// The comment of the entry is only added to its first unit.
+    notes: Vec<String>,
+    pattern: &'a ast::Pattern<&'s str>,
+}
+
+/// The translatable patterns of an FTL file.
+struct Units<'a, 's> {
+    units: Vec<Unit<'a, 's>>,
+    /// The notes for the comments after the last unit.
+    trailing_notes: Vec<String>,
+}
+
+fn units<'a, 's>(resource: &'a ast::Resource<&'s str>) -> Units<'a, 's> {
+    let mut units = Vec::new();
+    let mut notes = Vec::new();
+
+    for entry in &resource.body {
+        let (id, value, attributes, comment) = match entry {
+            ast::Entry::Message(message) => (
+                message.id.name.to_string(),
+                message.value.as_ref(),
+                &message.attributes,
+                message.comment.as_ref(),
+            ),
+            ast::Entry::Term(term) => (
+                format!("-{}", term.id.name),
+                Some(&term.value),
+                &term.attributes,
+                term.comment.as_ref(),
+            ),
+            ast::Entry::Comment(comment) => {
+                notes.extend(comment_notes(COMMENT_NOTES[0].1, comment));
+                continue;
+            }
+            ast::Entry::GroupComment(comment) => {
+                notes.extend(comment_notes(COMMENT_NOTES[1].1, comment));
+                continue;
+            }
+            ast::Entry::ResourceComment(comment) => {
+                notes.extend(comment_notes(COMMENT_NOTES[2].1, comment));
+                continue;
+            }
+            ast::Entry::Junk { .. } => continue,
+        };
+
+        let patterns = value
+            .into_iter()
+            .map(|pattern| (id.clone(), pattern))
+            .chain(
+                attributes
+                    .iter()
+                    .map(|attribute| (format!("{id}.{}", attribute.id.name), &attribute.value)),
+            );
+
+        for (index, (context, pattern)) in patterns.enumerate() {
+            if index == 0 {
+                notes.extend(
+                    comment
+                        .iter()
+                        .flat_map(|comment| comment.content.iter().map(ToString::to_string)),
+                );
+            }
+
+            units.push(Unit {
+                context,
+                notes: std::mem::take(&mut notes),
+                pattern,
+            });
+        }
+    }
+
+    Units {
+        units,
+        trailing_notes: notes,
+    }
+}
+
+/// Get the notes for a comment that doesn't belong to an entry, a note per line.
+fn comment_notes<'a>(
+    prefix: &'a str,
+    comment: &'a ast::Comment<&str>,
+) -> impl Iterator<Item = String> + 'a {
+    comment
+        .content
+        .iter()
+        .map(move |line| format!("{prefix}{line}"))
+}
+
+/// A comment that doesn't belong to an entry, read from the notes of a PO entry.
+struct FtlComment {
+    /// The FTL prefix of the comment, e.g. `##` for group comments.
+    prefix: &'static str,
+    lines: Vec<String>,
+}
+
+impl FtlComment {
+    /// Read the comments from the notes, merging consecutive lines of the same kind.
+    fn from_notes(notes: &[String]) -> Vec<Self> {
+        let mut comments: Vec<Self> = Vec::new();
+
+        for note in notes {
+            let Some((prefix, line)) = parse_comment_note(note) else {
+                continue;
+            };
+
+            match comments.last_mut() {
+                Some(comment) if comment.prefix == prefix => comment.lines.push(line.to_string()),
+                _ => comments.push(Self {
+                    prefix,
+                    lines: vec![line.to_string()],
+                }),
+            }
+        }
+
+        comments
+    }
+
+    fn to_source(&self) -> String {
+        let mut source = String::new();
+
+        for line in &self.lines {
+            if line.is_empty() {
+                let _ = writeln!(source, "{}", self.prefix);
+            } else {
+                let _ = writeln!(source, "{} {line}", self.prefix);
+            }
+        }
+
+        source
+    }
+}
+
+/// Get the FTL prefix and the line of a note storing a comment that doesn't belong to an entry.
+fn parse_comment_note(note: &str) -> Option<(&'static str, &str)> {
+    COMMENT_NOTES.iter().find_map(|(prefix, note_prefix)| {
+        // The space after an empty line is lost when the PO file is read
+        let line = note.strip_prefix(note_prefix.trim_end())?;
+        Some((*prefix, line.strip_prefix(' ').unwrap_or(line)))
+    })
+}
+
+/// A pattern that only consists of a selection on the plural categories.
+struct Plural<'a, 's> {
+    selector: &'a ast::InlineExpression<&'s str>,
+    variants: &'a [ast::Variant<&'s str>],
+}
+
+impl Plural<'_, '_> {
+    /// Get the source of the variant for the category, or of the default variant.
+    fn variant_source(&self, category: &str) -> String {
+        self.variants
+            .iter()
+            .find(|variant| {
+                matches!(variant.key, ast::VariantKey::Identifier { name } if name == category)
+            })
+            .or_else(|| self.variants.iter().find(|variant| variant.default))
+            .map(|variant| pattern_source(&variant.value))
+            .unwrap_or_default()
+    }
+}
+
+fn as_plural<'a, 's>(pattern: &'a ast::Pattern<&'s str>) -> Option<Plural<'a, 's>> {
+    let [ast::PatternElement::Placeable {
+        expression: ast::Expression::Select { selector, variants },
+    }] = pattern.elements.as_slice()
+    else {
+        return None;
+    };
+
+    let is_plural = variants.iter().all(|variant| {
+        matches!(variant.key, ast::VariantKey::Identifier { name } if PLURAL_CATEGORY_NAMES.contains(&name))
+    });
+
+    is_plural.then_some(Plural { selector, variants })
+}
+
+/// Get the Fluent source of the pattern, with multiple lines unindented.
+fn pattern_source(pattern: &ast::Pattern<&str>) -> String {
+    let resource = ast::Resource {
+        body: vec![ast::Entry::Message(ast::Message {
+            id: ast::Identifier { name: "x" },
+            value: Some(pattern.clone()),
+            attributes: Vec::new(),
+            comment: None,
+        })],
+    };
+
+    let source = serializer::serialize(&resource);
+    let value = source.strip_prefix("x =").unwrap_or_default();
+
+    let mut lines = value.lines();
+    let first_line = lines.next().unwrap_or_default().trim_start();
+
+    let lines: Vec<&str> = (!first_line.is_empty())
+        .then_some(first_line)
+        .into_iter()
+        .chain(lines.map(|line| line.strip_prefix("    ").unwrap_or(line)))
+        .collect();
+
+    lines.join("\n")
+}
+
+/// Get the Fluent source of an inline expression, e.g. `$count`.
+fn inline_source(expression: &ast::InlineExpression<&str>) -> String {
+    let pattern = ast::Pattern {
+        elements: vec![ast::PatternElement::Placeable {
+            expression: ast::Expression::Inline(expression.clone()),
+        }],
+    };
+
+    let source = pattern_source(&pattern);
+    source
+        .strip_prefix("{ ")
+        .and_then(|source| source.strip_suffix(" }"))
+        .unwrap_or(&source)
+        .to_string()
+}
+
+/// An entry of a PO file.
+#[derive(Default)]
+struct PoEntry {
+    /// The notes for the translators, i.e. `#.` comments.
+    notes: Vec<String>,
+    context: String,
+    id: String,
+    id_plural: Option<String>,
+    strings: Vec<String>,
+}
+
+impl PoEntry {
+    fn write(&self, po: &mut String) {
+        for note in &self.notes {
+            let _ = writeln!(po, "#. {note}");
+        }
+
+        write_po_string(po, "msgctxt", &self.context);
+        write_po_string(po, "msgid", &self.id);
+
+        match &self.id_plural {
+            Some(id_plural) => {
+                write_po_string(po, "msgid_plural", id_plural);
+
+                for (index, string) in self.strings.iter().enumerate() {
+                    write_po_string(po, &format!("msgstr[{index}]"), string);
+                }
+            }
+            None => write_po_string(
+                po,
+                "msgstr",
+                self.strings.first().map(String::as_str).unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// Write a keyword with a quoted string, split into a line per line of the string.
+fn write_po_string(po: &mut String, keyword: &str, value: &str) {
+    if !value.trim_end_matches('\n').contains('\n') {
+        let _ = writeln!(po, "{keyword} \"{}\"", escape(value));
+        return;
+    }
+
+    let _ = writeln!(po, "{keyword} \"\"");
+
+    for line in value.split_inclusive('\n') {
+        let _ = writeln!(po, "\"{}\"", escape(line));
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// An entry read from a PO file.
+#[derive(Default)]
+struct ParsedPoEntry {
+    /// The line the entry starts on.
+    line: usize,
+    notes: Vec<String>,
+    flags: Vec<String>,
+    context: Option<String>,
+    id: String,
+    id_plural: Option<String>,
+    strings: Vec<String>,
+}
+
+impl ParsedPoEntry {
+    fn error(&self, message: impl Into<String>) -> PoError {
+        PoError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    /// The lines of the entry comment, i.e. the notes that don't store anything for the import.
+    fn entry_comment(&self) -> Vec<String> {
+        self.notes
+            .iter()
+            .filter(|note| {
+                !note.starts_with(PLURAL_SELECTOR_NOTE) && parse_comment_note(note).is_none()
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Get the FTL source of the translation, or `None` if it is not translated.
+    fn ftl_value(&self, forms: Option<&PluralForms>) -> Result<Option<String>, PoError> {
+        // Partially translated plural entries are skipped as well
+        if self.strings.is_empty() || self.strings.iter().any(String::is_empty) {
+            return Ok(None);
+        }
+
+        if self.id_plural.is_none() {
+            return Ok(Some(self.strings[0].clone()));
+        }
+
+        let selector = self
+            .notes
+            .iter()
+            .find_map(|note| note.strip_prefix(PLURAL_SELECTOR_NOTE))
+            .ok_or_else(|| self.error("the plural entry has no plural selector note"))?;
+
+        let forms = forms.ok_or_else(|| self.error("the locale has no known plural forms"))?;
+
+        if forms.categories.len() != self.strings.len() {
+            return Err(self.error(format!(
+                "expected {} plural forms, found {}",
+                forms.categories.len(),
+                self.strings.len()
+            )));
+        }
+
+        // A single form doesn't need a selection
+        if self.strings.iter().all(|string| *string == self.strings[0]) {
+            return Ok(Some(self.strings[0].clone()));
+        }
+
+        let last_string = &self.strings[self.strings.len() - 1];
+        let variants: Vec<(&str, &String)> = PLURAL_CATEGORY_NAMES
+            .into_iter()
+            .filter_map(|category| {
+                match forms.categories.iter().position(|form| *form == category) {
+                    Some(index) => Some((category, &self.strings[index])),
+                    None => forms
+                        .extra_categories
+                        .contains(&category)
+                        .then_some((category, last_string)),
+                }
+            })
+            .collect();
+
+        let default_index = variants
+            .iter()
+            .position(|(category, _)| *category == "other")
+            .unwrap_or(variants.len() - 1);
+
+        let mut source = format!("{{ {selector} ->");
+
+        for (index, (category, string)) in variants.into_iter().enumerate() {
+            let marker = if index == default_index {
+                "   *"
+            } else {
+                "    "
+            };
+            let string = string.replace('\n', "\n        ");
+            let _ = write!(source, "\n{marker}[{category}] {string}");
+        }
+
+        source.push_str("\n}");
+        Ok(Some(source))
+    }
+}
+
+/// The part of an entry that a string continuation line belongs to.
+#[derive(Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    String(usize),
+}
+
+fn parse_po(source: &str) -> Result<Vec<ParsedPoEntry>, PoError> {
+    let mut entries = Vec::new();
+    let mut entry: Option<ParsedPoEntry> = None;
+    let mut field = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        let error = |message: &str| PoError {
+            line: line_number,
+            message: message.to_string(),
+        };
+
+        // Obsolete entries are ignored
+        if line.is_empty() || line.starts_with("#~") {
+            continue;
+        }
+
+        let starts_entry =
+            line.starts_with('#') || line.starts_with("msgctxt") || line.starts_with("msgid ");
+
+        // A comment or ID after the strings starts the next entry
+        if starts_entry
+            && entry
+                .as_ref()
+                .is_some_and(|entry| !entry.strings.is_empty())
+        {
+            entries.extend(entry.take());
+        }
+
+        let current = entry.get_or_insert_with(|| ParsedPoEntry {
+            line: line_number,
+            ..Default::default()
+        });
+
+        if let Some(note) = line.strip_prefix("#.") {
+            current
+                .notes
+                .push(note.strip_prefix(' ').unwrap_or(note).to_string());
+            continue;
+        }
+
+        if let Some(flags) = line.strip_prefix("#,") {
+            current
+                .flags
+                .extend(flags.split(',').map(|flag| flag.trim().to_string()));
+            continue;
+        }
+
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('"') {
+            let value = unquote(line).ok_or_else(|| error("invalid string"))?;
+
+            match field {
+                Some(Field::Context) => current
+                    .context
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::Id) => current.id.push_str(&value),
+                Some(Field::IdPlural) => current
+                    .id_plural
+                    .get_or_insert_with(String::new)
+                    .push_str(&value),
+                Some(Field::String(index)) => current.strings[index].push_str(&value),
+                None => return Err(error("string without keyword")),
+            }
+
+            continue;
+        }
+
+        let (keyword, value) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| error("expected a keyword and a string"))?;
+        let value = unquote(value.trim()).ok_or_else(|| error("invalid string"))?;
+
+        field = Some(match keyword {
+            "msgctxt" => {
+                current.context = Some(value);
+                Field::Context
+            }
+            "msgid" => {
+                current.id = value;
+                Field::Id
+            }
+            "msgid_plural" => {
+                current.id_plural = Some(value);
+                Field::IdPlural
+            }
+            "msgstr" => {
+                current.strings.push(value);
+                Field::String(current.strings.len() - 1)
+            }
+            _ => {
+                let index: usize = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse().ok())
+                    .ok_or_else(|| error(&format!("unknown keyword `{keyword}`")))?;
+
+                if index != current.strings.len() {
+                    return Err(error("the plural forms are not in order"));
+                }
+
+                current.strings.push(value);
+                Field::String(index)
+            }
+        });
+    }
+
+    entries.extend(entry);
+    Ok(entries)
+}
+
+/// Remove the quotes of a PO string and resolve its escapes.
+fn unquote(quoted: &str) -> Option<String> {
+    let inner = quoted.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+
+        match chars.next()? {
+            'n' => value.push('\n'),
+            't' => value.push('\t'),
+            'r' => value.push('\r'),
+            c => value.push(c),
+        }
+    }
+
+    Some(value)
+}
+
+/// An entry of the imported FTL file.
+struct FtlEntry {
+    /// The ID, prefixed with a `-` for terms.
+    id: String,
+    /// The line of the first PO entry of the FTL entry.
+    line: usize,
+    /// The comments that don't belong to an entry, before the entry.
+    comments: Vec<FtlComment>,
+    comment: Vec<String>,
+    value: Option<String>,
+    attributes: Vec<(String, String)>,
+}
+
+impl FtlEntry {
+    fn to_source(&self) -> String {
+        let mut source = String::new();
+
+        for line in &self.comment {
+            if line.is_empty() {
+                source.push_str("#\n");
+            } else {
+                let _ = writeln!(source, "# {line}");
+            }
+        }
+
+        source.push_str(&self.id);
+        source.push_str(" =");
+        write_ftl_pattern(&mut source, self.value.as_deref().unwrap_or_default(), 4);
+
+        for (name, value) in &self.attributes {
+            let _ = write!(source, "    .{name} =");
+            write_ftl_pattern(&mut source, value, 8);
+        }
+
+        source
+    }
+}
+
+/// Write a pattern after the `=`, on the same line or indented on the following lines.
+fn write_ftl_pattern(source: &mut String, pattern: &str, indent: usize) {
+    if pattern.is_empty() {
+        source.push('\n');
+        return;
+    }
+
+    if !pattern.contains('\n') {
+        let _ = writeln!(source, " {pattern}");
+        return;
+    }
+
+    source.push('\n');
+
+    for line in pattern.lines() {
+        if line.is_empty() {
+            source.push('\n');
+        } else {
+            let _ = writeln!(source, "{:indent$}{line}", "");
+        }
+    }
+}
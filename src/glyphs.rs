@@ -0,0 +1,125 @@
+//! Check if a font can display the localized messages.
+//!
+//! Fonts often only contain the glyphs for a limited set of scripts.
+//! The functions in this module find the characters of the messages that are not covered by a
+//! font, so that missing glyphs can be detected in tests instead of during playtesting.
+
+use std::borrow::Cow;
+
+use ab_glyph::Font as _;
+use bevy::{text::Font, utils::HashSet};
+use fluent::FluentResource;
+use fluent_syntax::ast;
+use unic_langid::LanguageIdentifier;
+
+use crate::fluent_visitor::{entry_patterns, walk_pattern, PatternVisitor};
+
+/// A message containing characters that are not covered by a font.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingGlyphs {
+    /// The locale of the message.
+    pub locale: LanguageIdentifier,
+    /// The ID of the message. Terms are prefixed with a `-`.
+    pub message_id: String,
+    /// The attribute of the message, if the characters are missing in an attribute.
+    pub attribute: Option<String>,
+    /// The characters that the font doesn't have a glyph for, in order of their first appearance.
+    pub characters: Vec<char>,
+}
+
+/// Find all messages in the FTL string that contain characters not covered by the font.
+///
+/// Only the static text of the messages is considered, i.e. the values of variables are not.
+/// Entries with syntax errors are skipped, the other entries of the string are still checked.
+///
+/// ```
+/// # use bevy::text::Font;
+/// # use bevy_mod_localization::glyphs::find_missing_glyphs;
+/// #
+/// let bytes = std::fs::read(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/fonts/FiraSans-Bold.ttf")).unwrap();
+/// let font = Font::try_from_bytes(bytes).unwrap();
+///
+/// let missing = find_missing_glyphs(&font, &"zh".parse().unwrap(), "hello = 你好 { $name }!");
+///
+/// assert_eq!(missing.len(), 1);
+/// assert_eq!(missing[0].message_id, "hello");
+/// assert_eq!(missing[0].characters, vec!['你', '好']);
+/// ```
+pub fn find_missing_glyphs(
+    font: &Font,
+    locale: &LanguageIdentifier,
+    ftl_string: &str,
+) -> Vec<MissingGlyphs> {
+    let resource =
+        FluentResource::try_new(ftl_string.to_string()).unwrap_or_else(|(resource, _)| resource);
+
+    find_missing_glyphs_in_resource(font, locale, &resource)
+}
+
+/// Find all messages in the resource that contain characters not covered by the font.
+pub(crate) fn find_missing_glyphs_in_resource(
+    font: &Font,
+    locale: &LanguageIdentifier,
+    resource: &FluentResource,
+) -> Vec<MissingGlyphs> {
+    let mut result = Vec::new();
+
+    for entry in resource.entries() {
+        let message_id = match entry {
+            ast::Entry::Message(message) => message.id.name.to_string(),
+            ast::Entry::Term(term) => format!("-{}", term.id.name),
+            _ => continue,
+        };
+        let Some((value, attributes)) = entry_patterns(entry) else {
+            continue;
+        };
+
+        let patterns = value.map(|value| (None, value)).into_iter().chain(
+            attributes
+                .iter()
+                .map(|attribute| (Some(attribute.id.name.to_string()), &attribute.value)),
+        );
+
+        for (attribute, pattern) in patterns {
+            let characters = missing_characters(font, pattern);
+
+            if !characters.is_empty() {
+                result.push(MissingGlyphs {
+                    locale: locale.clone(),
+                    message_id: message_id.clone(),
+                    attribute,
+                    characters,
+                });
+            }
+        }
+    }
+
+    result
+}
+
+/// Get the characters of the pattern's static text that are not covered by the font.
+fn missing_characters(font: &Font, pattern: &ast::Pattern<&str>) -> Vec<char> {
+    let mut text = TextCollector(String::new());
+    walk_pattern(pattern, &mut text);
+
+    let mut seen = HashSet::default();
+
+    text.0
+        .chars()
+        // Control characters like line breaks are not rendered as glyphs
+        .filter(|c| !c.is_control())
+        .filter(|c| font.font.glyph_id(*c).0 == 0)
+        .filter(|c| seen.insert(*c))
+        .collect()
+}
+
+/// Collects the displayed text of a pattern.
+///
+/// References are checked where they are defined, variables are only known at runtime.
+struct TextCollector(String);
+
+impl<'s> PatternVisitor<'s> for TextCollector {
+    fn visit_text(&mut self, text: Cow<'s, str>) {
+        self.0.push_str(&text);
+    }
+}
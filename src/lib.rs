@@ -1,9 +1,29 @@
+use std::{error::Error, fmt};
+
 use bevy::{prelude::*, reflect::TypeUuid};
+use fluent_syntax::parser::ParserError;
+
+use crate::locale::InvalidLocaleError;
 
+#[cfg(feature = "cli")]
+pub mod coverage;
+pub mod diagnostics;
+#[cfg(feature = "cli")]
+pub mod extract;
 mod fluent;
+mod fluent_visitor;
+#[cfg(feature = "cli")]
+mod ftl_files;
+#[cfg(feature = "cli")]
+pub mod gettext;
+pub mod glyphs;
+#[cfg(feature = "cli")]
+pub mod lint;
 mod loaders;
 pub mod locale;
 pub mod localization;
+#[cfg(feature = "bevy_ui")]
+pub mod mirror;
 pub mod plugin;
 pub mod prelude;
 
@@ -19,9 +39,52 @@ impl LocalizationSource {
     }
 }
 
-// TODO: Make this a proper error
-#[derive(PartialEq, Eq, Debug)]
-pub struct LocalizationError;
+/// An error of the localization API.
+#[derive(Debug, PartialEq)]
+pub enum LocalizationError {
+    /// The message is not defined for any locale of the resolution chain,
+    /// or it couldn't be formatted in any of them.
+    MessageNotFound,
+    /// No localization folder with the given path has been added.
+    FolderNotFound,
+    /// The locale is not a valid language tag.
+    InvalidLocale(InvalidLocaleError),
+    /// The FTL source contains syntax errors.
+    InvalidSource(Vec<ParserError>),
+    /// The writer returned an error.
+    Write(fmt::Error),
+}
+
+impl fmt::Display for LocalizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MessageNotFound => write!(f, "the message could not be found"),
+            Self::FolderNotFound => write!(f, "the localization folder could not be found"),
+            Self::InvalidLocale(err) => err.fmt(f),
+            Self::InvalidSource(errors) => {
+                write!(f, "the FTL source contains {} syntax errors", errors.len())
+            }
+            Self::Write(_) => write!(f, "the message could not be written"),
+        }
+    }
+}
+
+impl Error for LocalizationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidLocale(err) => Some(err),
+            Self::InvalidSource(errors) => errors.first().map(|err| err as &(dyn Error + 'static)),
+            Self::Write(err) => Some(err),
+            Self::MessageNotFound | Self::FolderNotFound => None,
+        }
+    }
+}
+
+impl From<InvalidLocaleError> for LocalizationError {
+    fn from(err: InvalidLocaleError) -> Self {
+        Self::InvalidLocale(err)
+    }
+}
 
 /// Idk what I'm doing
 pub struct LocalizationOutput;
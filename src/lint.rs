@@ -0,0 +1,433 @@
+//! Check the localization files of a folder for common mistakes.
+//!
+//! The checks are meant to run in CI, e.g. with the `lint` command of the
+//! `bevy_mod_localization` binary:
+//!
+//! ```text
+//! bevy_mod_localization lint assets/strings/menu --reference en-US
+//! ```
+//!
+//! Every locale is compared to a reference locale, usually the locale the strings are written in.
+//!
+//! This module requires the `cli` feature.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+use fluent::FluentResource;
+use fluent_syntax::ast;
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    fluent_visitor::{walk_entry, PatternVisitor},
+    ftl_files::{
+        entry_lines, line_of, plural_categories, read_ftl_files, FtlFile, PLURAL_CATEGORY_NAMES,
+    },
+    localization::{message_changes::entry_id, utils::get_ftl_path},
+};
+
+/// How severe a problem found by the linter is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The localization works, but is likely incomplete, e.g. falls back to another locale.
+    Warning,
+    /// The localization is broken, e.g. files can't be loaded or messages can't be formatted.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A problem found by the linter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LintKind {
+    /// The file contains invalid FTL syntax.
+    SyntaxError { message: String },
+    /// The file name is not a BCP 47 language tag in canonical form,
+    /// so the file is never loaded.
+    InvalidFileName {
+        /// The canonical file name, if the name is a valid language tag.
+        suggestion: Option<String>,
+    },
+    /// A message or term is defined more than once in the same file.
+    DuplicateId { id: String },
+    /// The folder doesn't contain a file for the reference locale.
+    MissingReferenceLocale { locale: LanguageIdentifier },
+    /// A message or term of the reference locale is missing.
+    MissingMessage { id: String },
+    /// A message or term is not defined in the reference locale.
+    ExtraMessage { id: String },
+    /// A message uses different variables than in the reference locale.
+    MismatchedVariables {
+        id: String,
+        /// The variables of the reference that the message doesn't use.
+        missing: Vec<String>,
+        /// The variables that the reference doesn't use.
+        extra: Vec<String>,
+    },
+    /// A plural selection doesn't have a variant for every plural category of the locale.
+    MissingPluralCategories { id: String, categories: Vec<String> },
+}
+
+impl LintKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintKind::SyntaxError { .. }
+            | LintKind::InvalidFileName { .. }
+            | LintKind::DuplicateId { .. }
+            | LintKind::MissingReferenceLocale { .. }
+            | LintKind::MismatchedVariables { .. } => Severity::Error,
+            LintKind::MissingMessage { .. }
+            | LintKind::ExtraMessage { .. }
+            | LintKind::MissingPluralCategories { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for LintKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintKind::SyntaxError { message } => write!(f, "syntax error: {message}"),
+            LintKind::InvalidFileName {
+                suggestion: Some(suggestion),
+            } => write!(
+                f,
+                "the file name is not a canonical language tag, rename it to `{suggestion}.ftl`"
+            ),
+            LintKind::InvalidFileName { suggestion: None } => {
+                write!(f, "the file name is not a valid language tag")
+            }
+            LintKind::DuplicateId { id } => write!(f, "`{id}` is defined more than once"),
+            LintKind::MissingReferenceLocale { locale } => {
+                write!(f, "the reference locale `{locale}` is missing")
+            }
+            LintKind::MissingMessage { id } => write!(f, "`{id}` is missing"),
+            LintKind::ExtraMessage { id } => {
+                write!(f, "`{id}` is not defined in the reference locale")
+            }
+            LintKind::MismatchedVariables { id, missing, extra } => {
+                write!(f, "`{id}` uses different variables than the reference")?;
+
+                if !missing.is_empty() {
+                    write!(f, ", missing: {}", format_variables(missing))?;
+                }
+
+                if !extra.is_empty() {
+                    write!(f, ", unknown: {}", format_variables(extra))?;
+                }
+
+                Ok(())
+            }
+            LintKind::MissingPluralCategories { id, categories } => write!(
+                f,
+                "`{id}` has no variants for the plural categories {}",
+                categories.join(", ")
+            ),
+        }
+    }
+}
+
+/// A problem found by the linter, together with its location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintDiagnostic {
+    /// The file containing the problem.
+    pub path: PathBuf,
+    /// The line of the problem, starting at 1, if it can be located.
+    pub line: Option<usize>,
+    pub kind: LintKind,
+}
+
+impl LintDiagnostic {
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.severity(), self.path.display())?;
+
+        if let Some(line) = self.line {
+            write!(f, ":{line}")?;
+        }
+
+        write!(f, ": {}", self.kind)
+    }
+}
+
+/// Check the localization files of the folder, comparing every locale to the reference locale.
+///
+/// ```
+/// # use bevy_mod_localization::lint::{lint_folder, Severity};
+/// #
+/// let folder = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/strings/interactive");
+/// let diagnostics = lint_folder(folder, &"en-US".parse().unwrap()).unwrap();
+///
+/// assert!(diagnostics.iter().all(|diagnostic| diagnostic.severity() < Severity::Error));
+/// ```
+pub fn lint_folder(
+    folder_path: impl AsRef<Path>,
+    reference_locale: &LanguageIdentifier,
+) -> io::Result<Vec<LintDiagnostic>> {
+    let folder_path = folder_path.as_ref();
+    let files = read_ftl_files(folder_path)?;
+    let mut diagnostics = Vec::new();
+
+    let parsed: Vec<(&FtlFile, FluentResource)> = files
+        .iter()
+        .map(|file| (file, lint_file(file, &mut diagnostics)))
+        .collect();
+
+    let reference = parsed
+        .iter()
+        .find(|(file, _)| file.canonical_locale() == Some(reference_locale))
+        .map(|(_, resource)| EntryInfos::new(resource));
+
+    if reference.is_none() {
+        diagnostics.push(LintDiagnostic {
+            path: get_ftl_path(&folder_path.to_string_lossy(), reference_locale),
+            line: None,
+            kind: LintKind::MissingReferenceLocale {
+                locale: reference_locale.clone(),
+            },
+        });
+    }
+
+    for (file, resource) in &parsed {
+        // Files with invalid names are never loaded
+        let Some(locale) = file.canonical_locale() else {
+            continue;
+        };
+
+        let entries = EntryInfos::new(resource);
+
+        if let Some(reference) = reference.as_ref().filter(|_| locale != reference_locale) {
+            compare_to_reference(file, &entries, reference, &mut diagnostics);
+        }
+
+        check_plural_categories(file, locale, &entries, &mut diagnostics);
+    }
+
+    diagnostics.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(diagnostics)
+}
+
+/// Check a single file on its own, returning the entries that could be parsed.
+fn lint_file(file: &FtlFile, diagnostics: &mut Vec<LintDiagnostic>) -> FluentResource {
+    let stem = file.stem();
+
+    match &file.locale {
+        Some(locale) if *locale == stem => {}
+        locale => diagnostics.push(LintDiagnostic {
+            path: file.path.clone(),
+            line: None,
+            kind: LintKind::InvalidFileName {
+                suggestion: locale.as_ref().map(ToString::to_string),
+            },
+        }),
+    }
+
+    let resource = match FluentResource::try_new(file.source.clone()) {
+        Ok(resource) => resource,
+        Err((resource, errors)) => {
+            for error in errors {
+                diagnostics.push(LintDiagnostic {
+                    path: file.path.clone(),
+                    line: Some(line_of(&file.source, error.pos.start)),
+                    kind: LintKind::SyntaxError {
+                        message: error.to_string(),
+                    },
+                });
+            }
+
+            resource
+        }
+    };
+
+    let mut seen = BTreeMap::<String, usize>::new();
+
+    for id in resource.entries().filter_map(entry_id) {
+        let count = seen.entry(id.clone()).or_default();
+        *count += 1;
+
+        if *count == 2 {
+            diagnostics.push(LintDiagnostic {
+                path: file.path.clone(),
+                line: entry_lines(&file.source, &id).get(1).copied(),
+                kind: LintKind::DuplicateId { id },
+            });
+        }
+    }
+
+    resource
+}
+
+fn compare_to_reference(
+    file: &FtlFile,
+    entries: &EntryInfos,
+    reference: &EntryInfos,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let line_of_entry = |id: &str| entry_lines(&file.source, id).first().copied();
+
+    for id in reference.0.keys().filter(|id| !entries.0.contains_key(*id)) {
+        diagnostics.push(LintDiagnostic {
+            path: file.path.clone(),
+            line: None,
+            kind: LintKind::MissingMessage { id: id.clone() },
+        });
+    }
+
+    for (id, info) in &entries.0 {
+        let Some(reference_info) = reference.0.get(id) else {
+            diagnostics.push(LintDiagnostic {
+                path: file.path.clone(),
+                line: line_of_entry(id),
+                kind: LintKind::ExtraMessage { id: id.clone() },
+            });
+            continue;
+        };
+
+        // The parameters of terms are passed explicitly, so only messages are compared
+        if id.starts_with('-') || info.variables == reference_info.variables {
+            continue;
+        }
+
+        diagnostics.push(LintDiagnostic {
+            path: file.path.clone(),
+            line: line_of_entry(id),
+            kind: LintKind::MismatchedVariables {
+                id: id.clone(),
+                missing: reference_info
+                    .variables
+                    .difference(&info.variables)
+                    .cloned()
+                    .collect(),
+                extra: info
+                    .variables
+                    .difference(&reference_info.variables)
+                    .cloned()
+                    .collect(),
+            },
+        });
+    }
+}
+
+fn check_plural_categories(
+    file: &FtlFile,
+    locale: &LanguageIdentifier,
+    entries: &EntryInfos,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let Some(categories) = plural_categories(locale) else {
+        return;
+    };
+
+    for (id, info) in &entries.0 {
+        let missing: BTreeSet<&str> = info
+            .plural_selections
+            .iter()
+            .flat_map(|keys| {
+                categories
+                    .iter()
+                    .copied()
+                    .filter(|category| !keys.contains(*category))
+            })
+            .collect();
+
+        if missing.is_empty() {
+            continue;
+        }
+
+        diagnostics.push(LintDiagnostic {
+            path: file.path.clone(),
+            line: entry_lines(&file.source, id).first().copied(),
+            kind: LintKind::MissingPluralCategories {
+                id: id.clone(),
+                categories: missing.into_iter().map(str::to_string).collect(),
+            },
+        });
+    }
+}
+
+/// The information about the messages and terms of a file needed for the checks.
+struct EntryInfos(BTreeMap<String, EntryInfo>);
+
+#[derive(Default)]
+struct EntryInfo {
+    variables: BTreeSet<String>,
+    /// The variant keys of every selection on a number.
+    plural_selections: Vec<BTreeSet<String>>,
+}
+
+impl EntryInfos {
+    fn new(resource: &FluentResource) -> Self {
+        let mut infos = BTreeMap::new();
+
+        for entry in resource.entries() {
+            let Some(id) = entry_id(entry) else {
+                continue;
+            };
+
+            let mut info = EntryInfo::default();
+            walk_entry(entry, &mut info);
+            infos.insert(id, info);
+        }
+
+        Self(infos)
+    }
+}
+
+impl<'s> PatternVisitor<'s> for EntryInfo {
+    fn visit_inline_expression(&mut self, expression: &ast::InlineExpression<&'s str>) {
+        if let ast::InlineExpression::VariableReference { id } = expression {
+            self.variables.insert(id.name.to_string());
+        }
+    }
+
+    fn visit_select(
+        &mut self,
+        selector: &ast::InlineExpression<&'s str>,
+        variants: &[ast::Variant<&'s str>],
+    ) {
+        let keys: BTreeSet<String> = variants
+            .iter()
+            .filter_map(|variant| match &variant.key {
+                ast::VariantKey::Identifier { name } => Some(name.to_string()),
+                ast::VariantKey::NumberLiteral { .. } => None,
+            })
+            .collect();
+
+        // Selections on strings, e.g. a gender, use other keys
+        let is_plural = matches!(
+            selector,
+            ast::InlineExpression::VariableReference { .. }
+                | ast::InlineExpression::FunctionReference { .. }
+                | ast::InlineExpression::NumberLiteral { .. }
+        ) && !keys.is_empty()
+            && keys
+                .iter()
+                .all(|key| PLURAL_CATEGORY_NAMES.contains(&key.as_str()));
+
+        if is_plural {
+            self.plural_selections.push(keys);
+        }
+    }
+}
+
+fn format_variables(variables: &[String]) -> String {
+    variables
+        .iter()
+        .map(|variable| format!("${variable}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use bevy::asset::{AssetLoader, LoadedAsset};
+
+use crate::locale::LocaleFallbackConfig;
+
+#[derive(Default)]
+pub struct FallbackConfigLoader;
+
+impl AssetLoader for FallbackConfigLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut bevy::asset::LoadContext,
+    ) -> bevy::asset::BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let config: LocaleFallbackConfig = ron::de::from_bytes(bytes)?;
+
+            load_context.set_default_asset(LoadedAsset::new(config));
+
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["fallbacks.ron"]
+    }
+}
@@ -1 +1,3 @@
+#[cfg(feature = "ron")]
+pub mod fallback_config_loader;
 pub mod ftl_loader;
@@ -1,12 +1,18 @@
 use bevy::prelude::*;
 use unic_langid::LanguageIdentifier;
 
-use super::into_language_identifier::IntoLanguageIdentifier;
+use super::into_language_identifier::{
+    IntoLanguageIdentifier, InvalidLocaleError, TryIntoLanguageIdentifier,
+};
 
 /// The default locale to fall back to.
 ///
 /// Locale resolution: [`Locale`] -> [`LocaleFallbackMap`] -> [`LocaleDefaultFallback`].
-#[derive(Debug, Default, Resource)]
+#[derive(Debug, Default, Clone, PartialEq, Resource, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "serde", reflect_value(Serialize, Deserialize))]
+#[reflect_value(Resource, Debug, PartialEq, Default)]
 pub struct LocaleDefaultFallback(pub(crate) Option<LanguageIdentifier>);
 
 impl LocaleDefaultFallback {
@@ -44,6 +50,22 @@ impl LocaleDefaultFallback {
         Self(locale.map(|into_locale| into_locale.into_language_identifier()))
     }
 
+    /// Create a new [`LocaleDefaultFallback`], returning an error if the locale is not a valid language tag.
+    pub fn try_new<T: TryIntoLanguageIdentifier>(
+        locale: Option<T>,
+    ) -> Result<Self, InvalidLocaleError> {
+        Ok(Self(
+            locale
+                .map(TryIntoLanguageIdentifier::try_into_language_identifier)
+                .transpose()?,
+        ))
+    }
+
+    /// The default fallback locale, if any.
+    pub fn locale(&self) -> Option<&LanguageIdentifier> {
+        self.0.as_ref()
+    }
+
     /// Change the default fallback locale.
     ///
     /// ```
@@ -61,4 +83,17 @@ impl LocaleDefaultFallback {
     pub fn set<T: IntoLanguageIdentifier>(&mut self, locale: Option<T>) {
         self.0 = locale.map(|into_locale| into_locale.into_language_identifier());
     }
+
+    /// Change the default fallback locale.
+    ///
+    /// If the locale is not a valid language tag, an error is returned and nothing is changed.
+    pub fn try_set<T: TryIntoLanguageIdentifier>(
+        &mut self,
+        locale: Option<T>,
+    ) -> Result<(), InvalidLocaleError> {
+        self.0 = locale
+            .map(TryIntoLanguageIdentifier::try_into_language_identifier)
+            .transpose()?;
+        Ok(())
+    }
 }
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use unic_langid::CharacterDirection;
+
+use super::Locale;
+use crate::localization::source_index::LocalizationSourceIndex;
+
+/// The text direction of the currently active [`Locale`].
+///
+/// This resource is updated automatically when the [`Locale`] changes.
+/// Like the localized text, it waits for the localization files of the new locale,
+/// see [`LocaleSwitchTimeout`](crate::localization::LocaleSwitchTimeout),
+/// so the layout changes in the same frame as the text.
+/// Use it to adapt your layout to right-to-left languages like Arabic or Hebrew:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// fn layout_system(direction: Res<LocaleDirection>) {
+///     if direction.is_changed() && direction.is_right_to_left() {
+///         // Update the layout
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct LocaleDirection(pub(crate) CharacterDirection);
+
+impl LocaleDirection {
+    /// The character direction of the active locale.
+    pub fn get(&self) -> CharacterDirection {
+        self.0
+    }
+
+    /// Determine if the active locale is written from right to left.
+    pub fn is_right_to_left(&self) -> bool {
+        self.0 == CharacterDirection::RTL
+    }
+}
+
+impl Default for LocaleDirection {
+    fn default() -> Self {
+        Self(CharacterDirection::LTR)
+    }
+}
+
+/// Update the [`LocaleDirection`] once the localizations have switched to the new locale.
+pub fn update_locale_direction(
+    mut direction: ResMut<LocaleDirection>,
+    locale: Res<Locale>,
+    source_index: Res<LocalizationSourceIndex>,
+) {
+    // The folders finish switching in the same frame that they swap their resolution chain
+    if (locale.is_changed() || source_index.is_changed()) && !source_index.is_switching_locale() {
+        let new_direction = LocaleDirection(locale.0.character_direction());
+
+        // Avoid triggering change detection if the direction stays the same
+        if *direction != new_direction {
+            *direction = new_direction;
+        }
+    }
+}
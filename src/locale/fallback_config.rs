@@ -0,0 +1,126 @@
+use bevy::{prelude::*, reflect::TypeUuid, utils::HashMap};
+use unic_langid::LanguageIdentifier;
+
+use super::{LocaleDefaultFallback, LocaleFallbackMap};
+use crate::plugin::LocalizationSet;
+
+/// The locale fallbacks defined in a config file.
+///
+/// The config file must have the extension `.fallbacks.ron`:
+///
+/// ```ron
+/// (
+///     default_fallback: Some("en-US"),
+///     fallbacks: {
+///         "pt-BR": ["pt-PT"],
+///         "es-MX": ["es-419", "es"],
+///     },
+///     transitive: false,
+/// )
+/// ```
+///
+/// All fields are optional.
+/// Use [`LoadLocaleFallbacks::load_locale_fallbacks`] to apply the config to your app.
+#[derive(Debug, Clone, Default, PartialEq, TypeUuid, serde::Deserialize)]
+#[uuid = "7d0d8c1a-5d3e-4a8b-9a0e-6a3c2f9e1b47"]
+pub struct LocaleFallbackConfig {
+    /// The locale to use for the [`LocaleDefaultFallback`].
+    #[serde(default)]
+    pub default_fallback: Option<LanguageIdentifier>,
+    /// The fallbacks to use for the [`LocaleFallbackMap`].
+    #[serde(default)]
+    pub fallbacks: HashMap<LanguageIdentifier, Vec<LanguageIdentifier>>,
+    /// Whether the fallbacks of the fallbacks are followed as well.
+    ///
+    /// See [`LocaleFallbackMap::set_transitive`].
+    #[serde(default)]
+    pub transitive: bool,
+}
+
+/// The config file that the locale fallbacks are loaded from.
+#[derive(Debug, Resource)]
+pub struct LocaleFallbackConfigHandle(pub Handle<LocaleFallbackConfig>);
+
+pub trait LoadLocaleFallbacks {
+    /// Load the [`LocaleFallbackMap`] and [`LocaleDefaultFallback`] from a config file.
+    ///
+    /// The path works like an asset path, i.e. by default, `assets` is the root of the path.
+    /// Once the file is loaded, it replaces the fallbacks defined in your app.
+    /// The fallbacks are updated again when the file is hot-reloaded,
+    /// and the fallbacks defined in your app are restored when the config is removed.
+    ///
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_mod_localization::prelude::*;
+    /// #
+    /// App::new()
+    ///     .insert_resource(Locale::new("es-MX"))
+    ///     .add_plugin(LocalizationPlugin)
+    ///     .load_locale_fallbacks("locales.fallbacks.ron")
+    ///     // -- snip --
+    ///     .run();
+    /// ```
+    fn load_locale_fallbacks(&mut self, path: &str) -> &mut Self;
+}
+
+impl LoadLocaleFallbacks for App {
+    fn load_locale_fallbacks(&mut self, path: &str) -> &mut Self {
+        let handle = self.world.resource::<AssetServer>().load(path);
+        let is_first = !self.world.contains_resource::<LocaleFallbackConfigHandle>();
+
+        self.insert_resource(LocaleFallbackConfigHandle(handle));
+
+        if is_first {
+            // Apply the fallbacks before the localizations check for changes
+            self.add_system(
+                update_fallbacks_on_config_change
+                    .in_base_set(CoreSet::Last)
+                    .before(LocalizationSet::HandleChanges),
+            );
+        }
+
+        self
+    }
+}
+
+/// Apply the fallback config when it has been loaded or modified.
+///
+/// The fallbacks that the config replaces are restored when it is removed.
+pub fn update_fallbacks_on_config_change(
+    config_handle: Res<LocaleFallbackConfigHandle>,
+    mut ev_asset: EventReader<AssetEvent<LocaleFallbackConfig>>,
+    configs: Res<Assets<LocaleFallbackConfig>>,
+    mut fallback_map: ResMut<LocaleFallbackMap>,
+    mut default_fallback: ResMut<LocaleDefaultFallback>,
+    mut replaced: Local<Option<(LocaleFallbackMap, LocaleDefaultFallback)>>,
+) {
+    for ev in ev_asset.iter() {
+        match ev {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                if *handle != config_handle.0 {
+                    continue;
+                }
+
+                if let Some(config) = configs.get(handle) {
+                    replaced
+                        .get_or_insert_with(|| (fallback_map.clone(), default_fallback.clone()));
+
+                    // Changing the resources triggers the recomputation of the resolution chains
+                    fallback_map.fallbacks = config.fallbacks.clone();
+                    fallback_map.transitive = config.transitive;
+                    default_fallback.0 = config.default_fallback.clone();
+                }
+            }
+            AssetEvent::Removed { handle } => {
+                if *handle != config_handle.0 {
+                    continue;
+                }
+
+                if let Some((replaced_map, replaced_default)) = replaced.take() {
+                    *fallback_map = replaced_map;
+                    *default_fallback = replaced_default;
+                }
+            }
+        }
+    }
+}
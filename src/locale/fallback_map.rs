@@ -1,8 +1,12 @@
+use std::fmt;
+
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 use unic_langid::LanguageIdentifier;
 
-use super::into_language_identifier::IntoLanguageIdentifier;
+use super::into_language_identifier::{
+    IntoLanguageIdentifier, InvalidLocaleError, TryIntoLanguageIdentifier,
+};
 
 /// A map of locales to fall back to.
 ///
@@ -10,8 +14,92 @@ use super::into_language_identifier::IntoLanguageIdentifier;
 /// from `en-US` instead, if available.
 ///
 /// Locale resolution: [`Locale`] -> [`LocaleFallbackMap`] -> [`LocaleDefaultFallback`].
-#[derive(Debug, Default, Resource)]
-pub struct LocaleFallbackMap(pub(crate) HashMap<LanguageIdentifier, Vec<LanguageIdentifier>>);
+///
+/// # Transitive fallbacks
+///
+/// By default, only the direct fallbacks of the [`Locale`] are used.
+/// In transitive mode, the fallbacks of the fallbacks are followed as well, depth-first.
+/// Every locale only appears once in the resolution chain.
+///
+/// ```
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// let mut fallback_map = LocaleFallbackMap::new();
+/// fallback_map.insert("es-MX", vec!["es-419"]);
+/// fallback_map.insert("es-419", vec!["es"]);
+/// fallback_map.set_transitive(true);
+///
+/// let chain = fallback_map.try_resolve("es-MX").unwrap();
+/// assert_eq!(chain, vec!["es-MX", "es-419", "es"]);
+/// ```
+///
+/// # Serialization
+///
+/// With the `serde` feature, the fallbacks are serialized as a map of BCP 47 strings,
+/// e.g. `{ "en-GB": ["en-US"] }`.
+/// Transitive fallback maps are serialized as `{ fallbacks: { ... }, transitive: true }`,
+/// both forms can be deserialized.
+///
+/// [`Locale`]: super::Locale
+/// [`LocaleDefaultFallback`]: super::LocaleDefaultFallback
+#[derive(Debug, Default, Clone, PartialEq, Resource, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "SerializedFallbackMap", into = "SerializedFallbackMap")
+)]
+#[cfg_attr(feature = "serde", reflect_value(Serialize, Deserialize))]
+#[reflect_value(Resource, Debug, PartialEq, Default)]
+pub struct LocaleFallbackMap {
+    pub(crate) fallbacks: HashMap<LanguageIdentifier, Vec<LanguageIdentifier>>,
+    pub(crate) transitive: bool,
+}
+
+/// The serialized form of the [`LocaleFallbackMap`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum SerializedFallbackMap {
+    WithOptions {
+        fallbacks: HashMap<LanguageIdentifier, Vec<LanguageIdentifier>>,
+        #[serde(default)]
+        transitive: bool,
+    },
+    Fallbacks(HashMap<LanguageIdentifier, Vec<LanguageIdentifier>>),
+}
+
+#[cfg(feature = "serde")]
+impl From<SerializedFallbackMap> for LocaleFallbackMap {
+    fn from(serialized: SerializedFallbackMap) -> Self {
+        match serialized {
+            SerializedFallbackMap::WithOptions {
+                fallbacks,
+                transitive,
+            } => Self {
+                fallbacks,
+                transitive,
+            },
+            SerializedFallbackMap::Fallbacks(fallbacks) => Self {
+                fallbacks,
+                transitive: false,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<LocaleFallbackMap> for SerializedFallbackMap {
+    fn from(fallback_map: LocaleFallbackMap) -> Self {
+        if fallback_map.transitive {
+            SerializedFallbackMap::WithOptions {
+                fallbacks: fallback_map.fallbacks,
+                transitive: true,
+            }
+        } else {
+            SerializedFallbackMap::Fallbacks(fallback_map.fallbacks)
+        }
+    }
+}
 
 impl LocaleFallbackMap {
     pub fn new() -> Self {
@@ -32,7 +120,7 @@ impl LocaleFallbackMap {
             .map(|x| x.into_language_identifier())
             .collect();
 
-        self.0.insert(key, value)
+        self.fallbacks.insert(key, value)
     }
 
     /// Add a new fallback language to the given locale.
@@ -47,7 +135,7 @@ impl LocaleFallbackMap {
         let key = locale.into_language_identifier();
         let value = fallback.into_language_identifier();
 
-        let cur_fallbacks = self.0.get_mut(&key);
+        let cur_fallbacks = self.fallbacks.get_mut(&key);
 
         if let Some(cur_fallbacks) = cur_fallbacks {
             if !cur_fallbacks.contains(&value) {
@@ -57,4 +145,174 @@ impl LocaleFallbackMap {
             self.insert(key, vec![value]);
         }
     }
+
+    /// Insert the fallbacks for the given locale.
+    ///
+    /// If any of the locales is not a valid language tag, an error is returned and nothing is
+    /// inserted.
+    ///
+    /// ```
+    /// # use bevy_mod_localization::prelude::*;
+    /// #
+    /// let mut fallback_map = LocaleFallbackMap::new();
+    ///
+    /// assert!(fallback_map.try_insert("en-GB", vec!["en-US"]).is_ok());
+    /// assert!(fallback_map.try_insert("en-AU", vec!["en GB"]).is_err());
+    /// ```
+    pub fn try_insert<K, V>(
+        &mut self,
+        locale: K,
+        fallbacks: Vec<V>,
+    ) -> Result<Option<Vec<LanguageIdentifier>>, InvalidLocaleError>
+    where
+        K: TryIntoLanguageIdentifier,
+        V: TryIntoLanguageIdentifier,
+    {
+        let key = locale.try_into_language_identifier()?;
+        let value = fallbacks
+            .into_iter()
+            .map(TryIntoLanguageIdentifier::try_into_language_identifier)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.fallbacks.insert(key, value))
+    }
+
+    /// Add a new fallback language to the given locale.
+    ///
+    /// If any of the locales is not a valid language tag, an error is returned and nothing is
+    /// added.
+    pub fn try_add_fallback<K, V>(
+        &mut self,
+        locale: K,
+        fallback: V,
+    ) -> Result<(), InvalidLocaleError>
+    where
+        K: TryIntoLanguageIdentifier,
+        V: TryIntoLanguageIdentifier,
+    {
+        let key = locale.try_into_language_identifier()?;
+        let value = fallback.try_into_language_identifier()?;
+        self.add_fallback(key, value);
+        Ok(())
+    }
+
+    /// Determine if the fallbacks of the fallbacks are followed as well.
+    pub fn is_transitive(&self) -> bool {
+        self.transitive
+    }
+
+    /// Set whether the fallbacks of the fallbacks should be followed as well.
+    ///
+    /// Disabled by default.
+    pub fn set_transitive(&mut self, transitive: bool) {
+        self.transitive = transitive;
+    }
+
+    /// Get the locales to try for the given locale, starting with the locale itself.
+    ///
+    /// The [`LocaleDefaultFallback`](super::LocaleDefaultFallback) is not included.
+    ///
+    /// Returns an error if the fallbacks form a cycle in transitive mode.
+    ///
+    /// ```
+    /// # use bevy_mod_localization::prelude::*;
+    /// #
+    /// let mut fallback_map = LocaleFallbackMap::new();
+    /// fallback_map.insert("pt-BR", vec!["pt-PT"]);
+    /// fallback_map.insert("pt-PT", vec!["pt-BR"]);
+    /// fallback_map.set_transitive(true);
+    ///
+    /// let err = fallback_map.try_resolve("pt-BR").unwrap_err();
+    /// assert_eq!(err.to_string(), "the locale fallbacks form a cycle: pt-BR -> pt-PT -> pt-BR");
+    /// ```
+    pub fn try_resolve<T: IntoLanguageIdentifier>(
+        &self,
+        locale: T,
+    ) -> Result<Vec<LanguageIdentifier>, FallbackCycleError> {
+        let (chain, cycle) = self.resolve(&locale.into_language_identifier());
+
+        match cycle {
+            Some(cycle) => Err(cycle),
+            None => Ok(chain),
+        }
+    }
+
+    /// Get the locales to try for the given locale, starting with the locale itself.
+    ///
+    /// If a cycle is found, the fallback closing it is skipped and the cycle is returned as well.
+    pub(crate) fn resolve(
+        &self,
+        locale: &LanguageIdentifier,
+    ) -> (Vec<LanguageIdentifier>, Option<FallbackCycleError>) {
+        let mut chain = vec![locale.clone()];
+
+        if !self.transitive {
+            if let Some(fallbacks) = self.fallbacks.get(locale) {
+                chain.extend(fallbacks.iter().cloned());
+            }
+
+            return (chain, None);
+        }
+
+        let mut path = vec![locale.clone()];
+        let mut cycle = None;
+        self.visit(locale, &mut path, &mut chain, &mut cycle);
+
+        (chain, cycle)
+    }
+
+    /// Add the fallbacks of the last locale in the path to the chain, depth-first.
+    fn visit(
+        &self,
+        locale: &LanguageIdentifier,
+        path: &mut Vec<LanguageIdentifier>,
+        chain: &mut Vec<LanguageIdentifier>,
+        cycle: &mut Option<FallbackCycleError>,
+    ) {
+        let Some(fallbacks) = self.fallbacks.get(locale) else {
+            return;
+        };
+
+        for fallback in fallbacks {
+            if let Some(start) = path.iter().position(|lang_id| lang_id == fallback) {
+                // Only report the first cycle
+                if cycle.is_none() {
+                    let mut locales = path[start..].to_vec();
+                    locales.push(fallback.clone());
+                    *cycle = Some(FallbackCycleError { locales });
+                }
+                continue;
+            }
+
+            // Reached through a different path before
+            if chain.contains(fallback) {
+                continue;
+            }
+
+            chain.push(fallback.clone());
+            path.push(fallback.clone());
+            self.visit(fallback, path, chain, cycle);
+            path.pop();
+        }
+    }
+}
+
+/// The transitive fallbacks of a locale form a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FallbackCycleError {
+    /// The locales of the cycle, starting and ending with the same locale.
+    pub locales: Vec<LanguageIdentifier>,
 }
+
+impl fmt::Display for FallbackCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let locales: Vec<String> = self.locales.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "the locale fallbacks form a cycle: {}",
+            locales.join(" -> ")
+        )
+    }
+}
+
+impl std::error::Error for FallbackCycleError {}
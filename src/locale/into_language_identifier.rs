@@ -1,7 +1,12 @@
-use std::str::FromStr;
-use unic_langid::LanguageIdentifier;
+use std::{error::Error, fmt, str::FromStr};
+use unic_langid::{LanguageIdentifier, LanguageIdentifierError};
 
 /// A helper trait to reduce boilerplate when creating a new [`Locale`].
+///
+/// Converting an invalid language tag panics.
+/// Use [`TryIntoLanguageIdentifier`] to validate user input instead.
+///
+/// [`Locale`]: super::Locale
 pub trait IntoLanguageIdentifier {
     fn into_language_identifier(self) -> LanguageIdentifier;
 }
@@ -23,3 +28,67 @@ impl IntoLanguageIdentifier for String {
         LanguageIdentifier::from_str(self.as_str()).expect("Invalid language ID")
     }
 }
+
+/// A fallible conversion into a language identifier, e.g. for locales entered by the user.
+///
+/// ```
+/// # use bevy_mod_localization::locale::TryIntoLanguageIdentifier;
+/// #
+/// assert!("en-US".try_into_language_identifier().is_ok());
+///
+/// let err = "not a locale".try_into_language_identifier().unwrap_err();
+/// assert_eq!(err.locale(), "not a locale");
+/// ```
+pub trait TryIntoLanguageIdentifier {
+    fn try_into_language_identifier(self) -> Result<LanguageIdentifier, InvalidLocaleError>;
+}
+
+impl TryIntoLanguageIdentifier for LanguageIdentifier {
+    fn try_into_language_identifier(self) -> Result<LanguageIdentifier, InvalidLocaleError> {
+        Ok(self)
+    }
+}
+
+impl TryIntoLanguageIdentifier for &str {
+    fn try_into_language_identifier(self) -> Result<LanguageIdentifier, InvalidLocaleError> {
+        LanguageIdentifier::from_str(self).map_err(|source| InvalidLocaleError {
+            locale: self.to_string(),
+            source,
+        })
+    }
+}
+
+impl TryIntoLanguageIdentifier for String {
+    fn try_into_language_identifier(self) -> Result<LanguageIdentifier, InvalidLocaleError> {
+        LanguageIdentifier::from_str(self.as_str()).map_err(|source| InvalidLocaleError {
+            locale: self,
+            source,
+        })
+    }
+}
+
+/// A string is not a valid unicode language tag.
+#[derive(Debug, PartialEq)]
+pub struct InvalidLocaleError {
+    locale: String,
+    source: LanguageIdentifierError,
+}
+
+impl InvalidLocaleError {
+    /// The string that failed to parse.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+}
+
+impl fmt::Display for InvalidLocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid locale `{}`: {}", self.locale, self.source)
+    }
+}
+
+impl Error for InvalidLocaleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
@@ -0,0 +1,169 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use unic_langid::LanguageIdentifier;
+
+use super::{
+    into_language_identifier::{
+        IntoLanguageIdentifier, InvalidLocaleError, TryIntoLanguageIdentifier,
+    },
+    LocaleDefaultFallback, LocaleFallbackMap,
+};
+use crate::localization::utils::get_resolution_chain;
+
+/// Use a different locale for a part of the UI.
+///
+/// When this component is placed on an entity, the [`LocalizedText`] and
+/// [`DynamicLocalizedText`] of the entity and all of its descendants use this locale
+/// instead of the global [`Locale`](super::Locale), e.g. for the UI of a second player.
+/// The [`LocaleFallbackMap`] and [`LocaleDefaultFallback`] apply to the overridden locale as well.
+/// If an entity has multiple ancestors with an override, the closest one is used.
+/// Entities that are moved into or out of an overridden hierarchy follow the new locale.
+///
+/// The localization files of the overridden locales are loaded in addition to the ones of the
+/// global locale, and they are dropped again once no override uses them anymore.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// fn spawn_player_two_ui(mut commands: Commands) {
+///     commands
+///         .spawn((NodeBundle::default(), LocaleOverride::new("de")))
+///         .with_children(|parent| {
+///             // -- snip: spawn the localized text of the second player --
+///         });
+/// }
+/// ```
+///
+/// [`LocalizedText`]: crate::localization::LocalizedText
+/// [`DynamicLocalizedText`]: crate::localization::DynamicLocalizedText
+#[derive(Debug, Default, Clone, PartialEq, Component, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect_value(Serialize, Deserialize))]
+#[reflect_value(Component, Debug, PartialEq, Default)]
+pub struct LocaleOverride {
+    locale: LanguageIdentifier,
+    /// The resolution chain of the locale, kept up to date by [`update_locale_overrides`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    resolution_chain: Vec<LanguageIdentifier>,
+}
+
+impl LocaleOverride {
+    /// Create a new [`LocaleOverride`] for the given locale.
+    pub fn new<T: IntoLanguageIdentifier>(locale: T) -> Self {
+        Self {
+            locale: locale.into_language_identifier(),
+            resolution_chain: Vec::new(),
+        }
+    }
+
+    /// Create a new [`LocaleOverride`], returning an error if the locale is not a valid language tag.
+    pub fn try_new<T: TryIntoLanguageIdentifier>(locale: T) -> Result<Self, InvalidLocaleError> {
+        Ok(Self {
+            locale: locale.try_into_language_identifier()?,
+            resolution_chain: Vec::new(),
+        })
+    }
+
+    /// The locale used in the hierarchy of the entity.
+    pub fn locale(&self) -> &LanguageIdentifier {
+        &self.locale
+    }
+
+    /// Change the locale used in the hierarchy of the entity.
+    pub fn set<T: IntoLanguageIdentifier>(&mut self, locale: T) {
+        self.locale = locale.into_language_identifier();
+    }
+
+    /// The locales that messages are looked up in, in order.
+    ///
+    /// The chain is determined at the end of the frame that the override has been changed in.
+    pub fn resolution_chain(&self) -> &[LanguageIdentifier] {
+        &self.resolution_chain
+    }
+}
+
+/// All locales that the [`LocaleOverride`] components need to be loaded.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct OverriddenLocales(pub(crate) Vec<LanguageIdentifier>);
+
+/// Access to the [`LocaleOverride`] that applies to an entity.
+#[derive(SystemParam)]
+pub(crate) struct LocaleOverrides<'w, 's> {
+    overrides: Query<'w, 's, &'static LocaleOverride>,
+    changed_overrides: Query<'w, 's, (), Changed<LocaleOverride>>,
+    removed_overrides: RemovedComponents<'w, 's, LocaleOverride>,
+    parents: Query<'w, 's, &'static Parent>,
+    changed_parents: Query<'w, 's, (), Changed<Parent>>,
+    removed_parents: RemovedComponents<'w, 's, Parent>,
+}
+
+impl<'w, 's> LocaleOverrides<'w, 's> {
+    /// Determine if any override has been changed, added or removed since the last run,
+    /// or if an entity might have been moved into or out of an overridden hierarchy.
+    pub(crate) fn is_changed(&mut self) -> bool {
+        // Consume all removal events, so they are only reported once
+        let removed = self.removed_overrides.iter().count() > 0;
+        let unparented = self.removed_parents.iter().count() > 0;
+        let reparented = unparented || !self.changed_parents.is_empty();
+
+        // Without any overrides, the hierarchy doesn't affect the locale
+        removed || !self.changed_overrides.is_empty() || (reparented && !self.overrides.is_empty())
+    }
+
+    /// Get the resolution chain of the closest override of the entity or its ancestors.
+    pub(crate) fn resolution_chain(&self, entity: Entity) -> Option<&[LanguageIdentifier]> {
+        let mut current = entity;
+
+        loop {
+            if let Ok(locale_override) = self.overrides.get(current) {
+                return Some(locale_override.resolution_chain());
+            }
+
+            current = self.parents.get(current).ok()?.get();
+        }
+    }
+}
+
+/// Compute the resolution chains of the overrides and collect the locales they need.
+pub fn update_locale_overrides(
+    mut overrides: Query<&mut LocaleOverride>,
+    mut removed_overrides: RemovedComponents<LocaleOverride>,
+    mut overridden_locales: ResMut<OverriddenLocales>,
+    fallback_map: Res<LocaleFallbackMap>,
+    default_fallback: Res<LocaleDefaultFallback>,
+) {
+    let fallbacks_changed = fallback_map.is_changed() || default_fallback.is_changed();
+    let mut changed = removed_overrides.iter().count() > 0;
+
+    for mut locale_override in overrides.iter_mut() {
+        if fallbacks_changed || locale_override.is_changed() {
+            let resolution_chain =
+                get_resolution_chain(&locale_override.locale, &fallback_map, &default_fallback);
+
+            if resolution_chain != locale_override.resolution_chain {
+                locale_override.resolution_chain = resolution_chain;
+            }
+
+            changed = true;
+        }
+    }
+
+    if !changed {
+        return;
+    }
+
+    let mut locales = Vec::new();
+
+    for locale_override in overrides.iter() {
+        for lang_id in &locale_override.resolution_chain {
+            if !locales.contains(lang_id) {
+                locales.push(lang_id.clone());
+            }
+        }
+    }
+
+    // Avoid triggering change detection if nothing changed
+    if locales != overridden_locales.0 {
+        overridden_locales.0 = locales;
+    }
+}
@@ -1,11 +1,25 @@
 mod default_fallback;
+pub(crate) mod direction;
+#[cfg(feature = "ron")]
+mod fallback_config;
 mod fallback_map;
 mod into_language_identifier;
+pub(crate) mod locale_override;
+#[cfg(feature = "ron")]
+pub(crate) mod persistence;
 
 use bevy::prelude::*;
 pub use default_fallback::LocaleDefaultFallback;
-pub use fallback_map::LocaleFallbackMap;
-pub use into_language_identifier::IntoLanguageIdentifier;
+pub use direction::LocaleDirection;
+#[cfg(feature = "ron")]
+pub use fallback_config::{LoadLocaleFallbacks, LocaleFallbackConfig, LocaleFallbackConfigHandle};
+pub use fallback_map::{FallbackCycleError, LocaleFallbackMap};
+pub use into_language_identifier::{
+    IntoLanguageIdentifier, InvalidLocaleError, TryIntoLanguageIdentifier,
+};
+pub use locale_override::LocaleOverride;
+#[cfg(feature = "ron")]
+pub use persistence::LocalePersistence;
 
 use unic_langid::LanguageIdentifier;
 
@@ -60,9 +74,30 @@ use unic_langid::LanguageIdentifier;
 ///
 /// Don't forget to add the system to your app.
 /// This method can be easily expanded to change the locale on a button press or other user input.
-#[derive(PartialEq, Eq, Debug, Clone, Resource)]
+///
+/// The resource is registered for reflection, so it can be edited in an inspector
+/// or inserted from a scene.
+#[derive(PartialEq, Eq, Debug, Clone, Resource, Reflect, FromReflect)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "serde", reflect_value(Serialize, Deserialize))]
+#[reflect_value(Resource, Debug, PartialEq)]
 pub struct Locale(pub(crate) LanguageIdentifier);
 
+/// There is no sensible default for the locale of an app, so it has to be inserted explicitly.
+/// When it is created without one, e.g. through reflection, the [`LocaleDefaultFallback`] is used,
+/// or the undetermined locale `und` if there is none.
+impl FromWorld for Locale {
+    fn from_world(world: &mut World) -> Self {
+        let locale = world
+            .get_resource::<LocaleDefaultFallback>()
+            .and_then(|default_fallback| default_fallback.0.clone())
+            .unwrap_or_default();
+
+        Self(locale)
+    }
+}
+
 impl Locale {
     /// Create a new [`Locale`].
     ///
@@ -97,6 +132,18 @@ impl Locale {
         Locale(locale.into_language_identifier())
     }
 
+    /// Create a new [`Locale`], returning an error if the locale is not a valid language tag.
+    ///
+    /// ```
+    /// # use bevy_mod_localization::prelude::*;
+    /// #
+    /// assert!(Locale::try_new("de-AT").is_ok());
+    /// assert!(Locale::try_new("de_AT!").is_err());
+    /// ```
+    pub fn try_new<T: TryIntoLanguageIdentifier>(locale: T) -> Result<Self, InvalidLocaleError> {
+        Ok(Locale(locale.try_into_language_identifier()?))
+    }
+
     /// Change the locale.
     ///
     /// Modifying the currently active locale is as simple as editing the corresponding resource:
@@ -116,4 +163,15 @@ impl Locale {
     pub fn set<T: IntoLanguageIdentifier>(&mut self, locale: T) {
         self.0 = locale.into_language_identifier();
     }
+
+    /// Change the locale, e.g. to a locale chosen by the user.
+    ///
+    /// If the locale is not a valid language tag, an error is returned and the locale is not changed.
+    pub fn try_set<T: TryIntoLanguageIdentifier>(
+        &mut self,
+        locale: T,
+    ) -> Result<(), InvalidLocaleError> {
+        self.0 = locale.try_into_language_identifier()?;
+        Ok(())
+    }
 }
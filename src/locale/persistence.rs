@@ -0,0 +1,179 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Locale, LocaleDefaultFallback, LocaleFallbackMap};
+
+/// Save the chosen locale across sessions.
+///
+/// When this resource is inserted before the [`LocalizationPlugin`](crate::plugin::LocalizationPlugin)
+/// is added, the locale settings are saved to a RON config file whenever they change.
+/// On startup, the saved settings are restored when the plugin is added,
+/// before any localization resolves its locales.
+///
+/// # Precedence
+///
+/// The saved settings take precedence over the resources inserted by your app.
+/// If you detect the locale of the operating system, insert it as the [`Locale`]:
+/// it will only be used on the first launch, until the player has chosen a locale.
+///
+/// 1. The saved settings of the player.
+/// 2. The [`Locale`] inserted by your app, e.g. detected from the operating system.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// App::new()
+///     .insert_resource(Locale::new("en-US"))
+///     .insert_resource(LocalePersistence::new("config"))
+///     .add_plugin(LocalizationPlugin)
+///     // -- snip --
+///     .run();
+/// ```
+///
+/// This resource requires the `ron` feature.
+#[derive(Debug, Clone, Resource)]
+pub struct LocalePersistence {
+    directory: PathBuf,
+    file_name: String,
+    persist_fallbacks: bool,
+    /// The settings that have been restored or saved last, to only save changed settings.
+    saved: Option<PersistedSettings>,
+}
+
+impl LocalePersistence {
+    /// Save the locale settings in the given directory.
+    ///
+    /// The directory is created if it doesn't exist yet.
+    /// By default, the [`LocaleFallbackMap`] and [`LocaleDefaultFallback`] are saved as well.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            file_name: "locale.ron".to_string(),
+            persist_fallbacks: true,
+            saved: None,
+        }
+    }
+
+    /// Use a different name for the config file.
+    ///
+    /// The default name is `locale.ron`.
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = file_name.into();
+        self
+    }
+
+    /// Set whether the [`LocaleFallbackMap`] and [`LocaleDefaultFallback`] should be saved.
+    pub fn with_fallbacks(mut self, persist_fallbacks: bool) -> Self {
+        self.persist_fallbacks = persist_fallbacks;
+        self
+    }
+
+    /// The path of the config file.
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(&self.file_name)
+    }
+
+    /// Restore the saved settings into the world.
+    ///
+    /// Nothing happens if no settings have been saved yet.
+    /// Afterwards, the settings of the world are remembered, so that they are only saved
+    /// once they change.
+    pub(crate) fn restore(&mut self, world: &mut World) {
+        let path = self.path();
+
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            match ron::from_str::<PersistedSettings>(&content) {
+                Ok(settings) => self.apply(settings, world),
+                Err(err) => warn!(
+                    "Ignoring the invalid locale settings in {}: {err}",
+                    path.display()
+                ),
+            }
+        }
+
+        self.saved = world.get_resource::<Locale>().map(|locale| {
+            self.settings(
+                locale,
+                world.resource::<LocaleFallbackMap>(),
+                world.resource::<LocaleDefaultFallback>(),
+            )
+        });
+    }
+
+    fn apply(&self, settings: PersistedSettings, world: &mut World) {
+        world.insert_resource(settings.locale);
+
+        if self.persist_fallbacks {
+            if let Some(fallback_map) = settings.fallback_map {
+                world.insert_resource(fallback_map);
+            }
+
+            if let Some(default_fallback) = settings.default_fallback {
+                world.insert_resource(default_fallback);
+            }
+        }
+    }
+
+    /// Get the settings to save.
+    fn settings(
+        &self,
+        locale: &Locale,
+        fallback_map: &LocaleFallbackMap,
+        default_fallback: &LocaleDefaultFallback,
+    ) -> PersistedSettings {
+        PersistedSettings {
+            locale: locale.clone(),
+            fallback_map: self.persist_fallbacks.then(|| fallback_map.clone()),
+            default_fallback: self.persist_fallbacks.then(|| default_fallback.clone()),
+        }
+    }
+
+    /// Save the given settings to the config file.
+    fn save(&self, settings: &PersistedSettings) -> std::io::Result<()> {
+        let content = ron::ser::to_string_pretty(settings, ron::ser::PrettyConfig::default())
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+
+        std::fs::create_dir_all(&self.directory)?;
+        std::fs::write(self.path(), content)
+    }
+}
+
+/// The content of the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PersistedSettings {
+    locale: Locale,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fallback_map: Option<LocaleFallbackMap>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_fallback: Option<LocaleDefaultFallback>,
+}
+
+/// Save the locale settings when they have been changed.
+pub fn save_locale_settings(
+    mut persistence: ResMut<LocalePersistence>,
+    locale: Res<Locale>,
+    fallback_map: Res<LocaleFallbackMap>,
+    default_fallback: Res<LocaleDefaultFallback>,
+) {
+    if !(locale.is_changed() || fallback_map.is_changed() || default_fallback.is_changed()) {
+        return;
+    }
+
+    let settings = persistence.settings(&locale, &fallback_map, &default_fallback);
+
+    // The restored settings are reported as changed as well
+    if persistence.saved.as_ref() == Some(&settings) {
+        return;
+    }
+
+    match persistence.save(&settings) {
+        Ok(()) => persistence.bypass_change_detection().saved = Some(settings),
+        Err(err) => error!(
+            "Failed to save the locale settings to {}: {err}",
+            persistence.path().display()
+        ),
+    }
+}
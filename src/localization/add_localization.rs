@@ -1,16 +1,19 @@
 use crate::{
+    diagnostics::{measure_localization, LocalizationMeasurements},
+    locale::locale_override::OverriddenLocales,
     plugin::LocalizationSet,
     prelude::{Locale, LocaleDefaultFallback, LocaleFallbackMap},
     LocalizationSource,
 };
 
 use super::{
+    source_index::route_localization_asset_events,
     systems::{
         update_localization_on_asset_change, update_localization_on_locale_change,
         update_localized_text,
     },
-    utils::{get_ftl_path, get_resolution_chain},
-    Localization, LocalizationFolder,
+    utils::get_resolution_chain,
+    Localization, LocalizationFolder, LocalizationLoadPolicy, LocalizedText,
 };
 use bevy::prelude::*;
 
@@ -24,30 +27,34 @@ impl AddLocalization for App {
             return self;
         }
 
-        let asset_server = self.world.resource::<AssetServer>();
-
         let locale = self.world.resource::<Locale>();
         let fallback_map = self.world.resource::<LocaleFallbackMap>();
         let default_fallback = self.world.resource::<LocaleDefaultFallback>();
 
-        let resolution_chain = get_resolution_chain(locale, fallback_map, default_fallback);
+        let resolution_chain = get_resolution_chain(&locale.0, fallback_map, default_fallback);
+        let override_locales = self.world.resource::<OverriddenLocales>().0.clone();
+        let load_policy = self.world.resource::<LocalizationLoadPolicy>().clone();
 
-        let mut localization = Localization::<T>::new(resolution_chain.clone());
+        let asset_server = self.world.resource::<AssetServer>().clone();
+        let mut assets = self.world.resource_mut::<Assets<LocalizationSource>>();
 
         // Initiate loading of the localization files
-        for lang_id in resolution_chain {
-            let ftl_path = get_ftl_path::<T>(&lang_id);
-            let handle: Handle<LocalizationSource> = asset_server.load(ftl_path);
-            localization.handle_map.insert(lang_id.clone(), handle);
-        }
+        let mut localization = Localization::<T>::default();
+        localization.folder.override_locales = override_locales;
+        localization.folder.load_policy = load_policy;
+        localization
+            .folder
+            .set_resolution_chain(resolution_chain, &asset_server, &mut assets);
+
+        self.register_type::<LocalizedText<T>>();
 
         self.insert_resource(localization).add_systems(
             (
                 // First, check if the locale changed
-                update_localization_on_locale_change::<T>,
+                update_localization_on_locale_change::<T>.before(route_localization_asset_events),
                 // Then check if the asset changed
                 // A locale change will also reload the assets, so this has to happen afterwards
-                update_localization_on_asset_change::<T>,
+                update_localization_on_asset_change::<T>.after(route_localization_asset_events),
                 // Update localized text components
                 update_localized_text::<T>,
             )
@@ -55,6 +62,12 @@ impl AddLocalization for App {
                 .in_set(LocalizationSet::HandleChanges),
         );
 
+        self.add_system(
+            measure_localization::<T>
+                .run_if(resource_exists::<LocalizationMeasurements>())
+                .in_set(LocalizationSet::Diagnostics),
+        );
+
         self
     }
 }
@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{borrow::Cow, marker::PhantomData};
 
 use bevy::prelude::*;
 
@@ -8,23 +8,74 @@ use super::LocalizationFolder;
 ///
 /// The first section of the [`Text`] component will be updated with the specified message.
 /// This will be updated every time the locale or localization file changes.
-#[derive(Component)]
+/// Components that are spawned or changed are localized at the end of the same frame.
+#[derive(Component, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
 pub struct LocalizedText<T: LocalizationFolder> {
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
     phantom: PhantomData<T>,
-    message_id: &'static str,
+    message_id: Cow<'static, str>,
 }
 
 impl<T: LocalizationFolder> LocalizedText<T> {
     /// Create a new localzed text with the given message ID.
-    pub fn new(message_id: &'static str) -> Self {
+    pub fn new(message_id: impl Into<Cow<'static, str>>) -> Self {
         Self {
             phantom: PhantomData,
-            message_id,
+            message_id: message_id.into(),
         }
     }
 
     /// Get the message ID of the localized text.
-    pub fn message_id(&self) -> &'static str {
-        self.message_id
+    ///
+    /// The ID is not `&'static` anymore, because components deserialized from scenes
+    /// or created through reflection own their message ID.
+    pub fn message_id(&self) -> &str {
+        &self.message_id
+    }
+}
+
+impl<T: LocalizationFolder> Default for LocalizedText<T> {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+/// Automatically localize an entity with a [`Text`] component, using a runtime folder.
+///
+/// This is the counterpart of [`LocalizedText`] for folders registered in the
+/// [`Localizations`](super::Localizations) resource.
+/// The first section of the [`Text`] component will be updated with the specified message.
+#[derive(Debug, Default, Clone, Component, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DynamicLocalizedText {
+    folder_path: String,
+    message_id: String,
+}
+
+impl DynamicLocalizedText {
+    /// Create a new localized text with the given folder path and message ID.
+    pub fn new(folder_path: impl Into<String>, message_id: impl Into<String>) -> Self {
+        Self {
+            folder_path: folder_path.into(),
+            message_id: message_id.into(),
+        }
+    }
+
+    /// Get the path of the folder containing the message.
+    pub fn folder_path(&self) -> &str {
+        &self.folder_path
+    }
+
+    /// Get the message ID of the localized text.
+    ///
+    /// The ID is not `&'static` anymore, because components deserialized from scenes
+    /// or created through reflection own their message ID.
+    pub fn message_id(&self) -> &str {
+        &self.message_id
     }
 }
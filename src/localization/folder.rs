@@ -29,7 +29,42 @@
 /// │  │  │  ├─ de.ftl
 /// │  │  │  ├─ fr.ftl
 /// ```
+///
+/// # Embedding
+///
+/// If your app is shipped without an assets folder, the `.ftl` files can be embedded
+/// into the binary with the `#[embed]` attribute:
+///
+/// ```
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// #[derive(LocalizationFolder)]
+/// #[folder_path = "strings/basic"]
+/// #[embed]
+/// struct EmbeddedLocalizationFolder;
+///
+/// assert!(EmbeddedLocalizationFolder::EMBEDDED_SOURCES.is_some());
+/// ```
+///
+/// The files are read from the folder at compile time, so they are not hot-reloaded.
+/// Use `#[embed = "path/to/assets"]` if your asset folder is not located at `/assets`.
+///
+/// Changes to the embedded files trigger a rebuild, but cargo doesn't notice when a file is
+/// added to or removed from the folder. Touch the file containing the derive or run
+/// `cargo clean` in that case, or let a build script track the folder:
+///
+/// ```text
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rerun-if-changed=assets/strings/basic");
+/// }
+/// ```
 // TODO: Review if the 'static is really needed for world.contains_resource
 pub trait LocalizationFolder: 'static + std::marker::Send + std::marker::Sync {
     const FOLDER_PATH: &'static str;
+
+    /// The embedded localization files, as pairs of language ID and FTL string.
+    ///
+    /// If this is `Some`, the files are not loaded from the asset folder.
+    const EMBEDDED_SOURCES: Option<&'static [(&'static str, &'static str)]> = None;
 }
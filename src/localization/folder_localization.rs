@@ -0,0 +1,962 @@
+use std::{fmt, path::Path, sync::Arc, time::Duration};
+
+use bevy::{
+    asset::{HandleId, LoadState},
+    prelude::*,
+    utils::{HashMap, Instant},
+};
+use fluent::{FluentArgs, FluentResource};
+use fluent_syntax::ast;
+use unic_langid::LanguageIdentifier;
+
+use super::{
+    format_cache::{FormatCache, FormatCacheStats},
+    message_changes::{changed_messages, entry_id, MessageGenerations, MessageSource},
+    metrics::LocalizationMetrics,
+    utils::{asset_folder_path, get_ftl_path},
+    LocalizationLoadPolicy,
+};
+use crate::{
+    fluent::FluentBundle,
+    glyphs::{find_missing_glyphs_in_resource, MissingGlyphs},
+    locale::TryIntoLanguageIdentifier,
+    LocalizationError, LocalizationSource,
+};
+
+/// The source that a message has been taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageLayer<'a> {
+    /// The message is defined in the localization files of the given folder.
+    Folder(&'a str),
+    /// The message is defined in a source inserted from memory.
+    Inserted,
+}
+
+/// A resolution chain that replaces the active one once its localization files are loaded.
+pub(crate) struct PendingChain {
+    pub(crate) resolution_chain: Vec<LanguageIdentifier>,
+    /// The time of the first check whether the files are loaded.
+    started: Option<Duration>,
+}
+
+/// The loaded localization of a single folder.
+///
+/// This keeps track of the localization files of every locale in the resolution chain,
+/// as well as the parsed bundles once the files are loaded.
+/// It backs both the typed [`Localization`](super::Localization) resource
+/// and the runtime folders of the [`Localizations`](super::Localizations) resource.
+///
+/// # Layers
+///
+/// Additional folders can be layered on top of the localization folder, e.g. for DLCs or mods.
+/// For every locale, the files of all layers are merged into one bundle.
+/// Later layers take precedence, i.e. their messages override the messages of earlier layers.
+/// Layers don't need to provide files for every locale.
+///
+/// # Switching locales
+///
+/// When the locale changes, the previous resolution chain stays active until the localization
+/// files of the new chain are loaded, see [`LocaleSwitchTimeout`](super::LocaleSwitchTimeout).
+pub struct FolderLocalization {
+    pub(crate) folder_path: String,
+    /// The localization files embedded into the binary, replacing the files of the base folder.
+    pub(crate) embedded_sources: Option<&'static [(&'static str, &'static str)]>,
+    /// The folders layered on top of the base folder, in order of increasing precedence.
+    pub(crate) layers: Vec<String>,
+    /// The localization files of each locale, one for each layer (the base folder first).
+    pub(crate) handle_map: HashMap<LanguageIdentifier, Vec<Handle<LocalizationSource>>>,
+    /// The locale and layer of each localization file, to look up asset events directly.
+    pub(crate) source_index: HashMap<HandleId, (LanguageIdentifier, usize)>,
+    /// The parsed localization files of each locale, if they are loaded.
+    pub(crate) resource_map: HashMap<LanguageIdentifier, Vec<Option<Arc<FluentResource>>>>,
+    /// The sources inserted from memory, taking precedence over all layers.
+    pub(crate) inserted_sources: HashMap<LanguageIdentifier, Vec<Arc<FluentResource>>>,
+    pub(crate) bundle_map: HashMap<LanguageIdentifier, FluentBundle>,
+    /// The entry that each message and term of a bundle has been taken from.
+    pub(crate) message_sources: HashMap<LanguageIdentifier, HashMap<String, MessageSource>>,
+    /// Which messages have changed, to only update the affected text.
+    pub(crate) generations: MessageGenerations,
+    /// The cache of formatted messages, if enabled.
+    pub(crate) format_cache: Option<FormatCache>,
+    /// The work done by the localization, for the diagnostics.
+    pub(crate) metrics: LocalizationMetrics,
+    pub(crate) resolution_chain: Vec<LanguageIdentifier>,
+    /// The resolution chain to switch to once its files are loaded.
+    pub(crate) pending_chain: Option<PendingChain>,
+    /// The locales needed by [`LocaleOverride`](crate::locale::LocaleOverride) components,
+    /// loaded in addition to the resolution chain.
+    pub(crate) override_locales: Vec<LanguageIdentifier>,
+    /// Which other locales are kept loaded.
+    pub(crate) load_policy: LocalizationLoadPolicy,
+    /// The locales kept loaded because of the load policy.
+    pub(crate) preloaded_locales: Vec<LanguageIdentifier>,
+    /// The locales that the layers provide files for, listed once for the load policy.
+    pub(crate) available_locales: Option<Vec<LanguageIdentifier>>,
+    /// Whether the layers have been changed since the files have last been loaded.
+    pub(crate) layers_changed: bool,
+    /// Whether the localization files have changed since they have last been indexed.
+    pub(crate) sources_changed: bool,
+}
+
+impl FolderLocalization {
+    pub(crate) fn new(folder_path: impl Into<String>) -> Self {
+        Self {
+            folder_path: folder_path.into(),
+            embedded_sources: None,
+            layers: Vec::new(),
+            handle_map: HashMap::default(),
+            source_index: HashMap::default(),
+            resource_map: HashMap::default(),
+            inserted_sources: HashMap::default(),
+            bundle_map: HashMap::default(),
+            message_sources: HashMap::default(),
+            generations: MessageGenerations::default(),
+            format_cache: None,
+            metrics: LocalizationMetrics::default(),
+            resolution_chain: Vec::new(),
+            pending_chain: None,
+            override_locales: Vec::new(),
+            load_policy: LocalizationLoadPolicy::default(),
+            preloaded_locales: Vec::new(),
+            available_locales: None,
+            layers_changed: false,
+            sources_changed: false,
+        }
+    }
+
+    /// The path of the localization folder, relative to the asset folder.
+    pub fn folder_path(&self) -> &str {
+        &self.folder_path
+    }
+
+    /// The locales that messages are looked up in, in order.
+    ///
+    /// The chain starts with the [`Locale`](crate::locale::Locale), followed by its fallbacks
+    /// and the [`LocaleDefaultFallback`](crate::locale::LocaleDefaultFallback).
+    pub fn resolution_chain(&self) -> &[LanguageIdentifier] {
+        &self.resolution_chain
+    }
+
+    /// The resolution chain that is used once its localization files are loaded, if any.
+    pub fn pending_resolution_chain(&self) -> Option<&[LanguageIdentifier]> {
+        self.pending_chain
+            .as_ref()
+            .map(|pending| pending.resolution_chain.as_slice())
+    }
+
+    /// The resolution chain that the localization is using or switching to.
+    pub(crate) fn target_resolution_chain(&self) -> &[LanguageIdentifier] {
+        self.pending_resolution_chain()
+            .unwrap_or(&self.resolution_chain)
+    }
+
+    /// The folders layered on top of the localization folder, in order of increasing precedence.
+    pub fn layers(&self) -> &[String] {
+        &self.layers
+    }
+
+    /// Add a folder as the new top layer, overriding the messages of all previous layers.
+    ///
+    /// The path works like an asset path, i.e. by default, `assets` is the root of the path.
+    /// The localization files of the layer are loaded in the next update.
+    ///
+    /// Returns `false` if the folder is already part of the localization.
+    pub fn add_layer(&mut self, layer_path: impl Into<String>) -> bool {
+        let layer_path = layer_path.into();
+
+        if self.layer_paths().any(|path| path == layer_path) {
+            return false;
+        }
+
+        self.layers.push(layer_path);
+        self.layers_changed = true;
+        true
+    }
+
+    /// Remove a previously added layer.
+    ///
+    /// Returns `false` if the folder was not a layer of the localization.
+    pub fn remove_layer(&mut self, layer_path: &str) -> bool {
+        let len = self.layers.len();
+        self.layers.retain(|path| path != layer_path);
+
+        if self.layers.len() == len {
+            return false;
+        }
+
+        self.layers_changed = true;
+        true
+    }
+
+    /// Get the source that the given message is currently taken from.
+    ///
+    /// This is either the localization folder itself, one of its layers
+    /// or a source inserted with [`FolderLocalization::insert_source`].
+    /// The message is resolved the same way as [`FolderLocalization::try_get_message`].
+    pub fn message_layer(&self, message_id: &str) -> Option<MessageLayer<'_>> {
+        self.resolution_chain
+            .iter()
+            .find_map(|lang_id| self.message_sources.get(lang_id)?.get(message_id))
+            .map(|source| {
+                self.layer_paths()
+                    .nth(source.layer)
+                    .map_or(MessageLayer::Inserted, MessageLayer::Folder)
+            })
+    }
+
+    /// Insert a localization source for the given locale, bypassing the asset server.
+    ///
+    /// This can be used for strings that don't come from the asset folder,
+    /// e.g. from save data, tests or a debug console.
+    /// Inserted sources take precedence over all folders,
+    /// later insertions override the messages of earlier ones.
+    /// The source is parsed immediately and kept when the locale changes.
+    pub fn insert_source<T: TryIntoLanguageIdentifier>(
+        &mut self,
+        locale: T,
+        ftl_string: impl Into<String>,
+    ) -> Result<(), LocalizationError> {
+        let lang_id = locale.try_into_language_identifier()?;
+        let resource = FluentResource::try_new(ftl_string.into())
+            .map_err(|(_, errors)| LocalizationError::InvalidSource(errors))?;
+
+        self.inserted_sources
+            .entry(lang_id.clone())
+            .or_default()
+            .push(Arc::new(resource));
+        self.rebuild_bundle(&lang_id);
+
+        Ok(())
+    }
+
+    /// Remove all sources that have been inserted for the given locale.
+    ///
+    /// Returns `false` if no sources had been inserted for the locale.
+    pub fn remove_sources<T: TryIntoLanguageIdentifier>(&mut self, locale: T) -> bool {
+        let Ok(lang_id) = locale.try_into_language_identifier() else {
+            return false;
+        };
+
+        if self.inserted_sources.remove(&lang_id).is_none() {
+            return false;
+        }
+
+        self.rebuild_bundle(&lang_id);
+        true
+    }
+
+    /// Find all messages that contain characters not covered by the given font.
+    ///
+    /// All loaded locales of the resolution chain and of the locale overrides are checked.
+    /// Messages that are overridden by a later layer are only checked in that layer.
+    /// See the [`glyphs`](crate::glyphs) module for more information.
+    pub fn find_missing_glyphs(&self, font: &Font) -> Vec<MissingGlyphs> {
+        let mut result = Vec::new();
+
+        for lang_id in self.loaded_locales() {
+            let resources = self
+                .resource_map
+                .get(lang_id)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .chain(self.inserted_sources.get(lang_id).into_iter().flatten());
+
+            // Later layers override the messages of earlier ones
+            let mut messages: HashMap<String, Vec<MissingGlyphs>> = HashMap::default();
+
+            for resource in resources {
+                for entry in resource.entries() {
+                    match entry {
+                        ast::Entry::Message(message) => {
+                            messages.remove(message.id.name);
+                        }
+                        ast::Entry::Term(term) => {
+                            messages.remove(&format!("-{}", term.id.name));
+                        }
+                        _ => {}
+                    }
+                }
+
+                for missing in find_missing_glyphs_in_resource(font, lang_id, resource) {
+                    messages
+                        .entry(missing.message_id.clone())
+                        .or_default()
+                        .push(missing);
+                }
+            }
+
+            let mut missing: Vec<MissingGlyphs> = messages.into_values().flatten().collect();
+            missing
+                .sort_by(|a, b| (&a.message_id, &a.attribute).cmp(&(&b.message_id, &b.attribute)));
+            result.append(&mut missing);
+        }
+
+        result
+    }
+
+    /// Iterate over the folder paths of all layers, including the base folder.
+    fn layer_paths(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.folder_path.as_str()).chain(self.layers.iter().map(String::as_str))
+    }
+
+    /// Get the locales that bundles are kept for.
+    ///
+    /// These are the locales of the active and pending resolution chains, followed by the
+    /// overridden locales and the locales kept loaded by the load policy.
+    fn loaded_locales(&self) -> Vec<&LanguageIdentifier> {
+        let mut locales = Vec::new();
+
+        for lang_id in self
+            .resolution_chain
+            .iter()
+            .chain(self.pending_resolution_chain().into_iter().flatten())
+            .chain(&self.override_locales)
+            .chain(&self.preloaded_locales)
+        {
+            if !locales.contains(&lang_id) {
+                locales.push(lang_id);
+            }
+        }
+
+        locales
+    }
+
+    /// Determine if bundles are kept for the given locale.
+    fn is_loaded(&self, lang_id: &LanguageIdentifier) -> bool {
+        self.is_active(lang_id)
+            || self
+                .pending_resolution_chain()
+                .is_some_and(|chain| chain.contains(lang_id))
+            || self.preloaded_locales.contains(lang_id)
+    }
+
+    /// Determine if messages are currently looked up in the given locale.
+    fn is_active(&self, lang_id: &LanguageIdentifier) -> bool {
+        self.resolution_chain.contains(lang_id) || self.override_locales.contains(lang_id)
+    }
+
+    /// Determine if all localization files of the given locale have finished loading.
+    ///
+    /// Files that failed to load count as finished, since layers don't need to provide every locale.
+    fn is_ready(&self, lang_id: &LanguageIdentifier, asset_server: &AssetServer) -> bool {
+        let (Some(handles), Some(resources)) =
+            (self.handle_map.get(lang_id), self.resource_map.get(lang_id))
+        else {
+            return false;
+        };
+
+        handles
+            .iter()
+            .zip(resources)
+            .enumerate()
+            .all(|(layer, (handle, resource))| {
+                resource.is_some()
+                    // Embedded files are added immediately, if they exist
+                    || (layer == 0 && self.embedded_sources.is_some())
+                    || asset_server.get_load_state(handle) == LoadState::Failed
+            })
+    }
+
+    /// Determine the locales to keep loaded because of the load policy.
+    fn policy_locales(&mut self, asset_server: &AssetServer) -> Vec<LanguageIdentifier> {
+        match &self.load_policy {
+            LocalizationLoadPolicy::OnlyChain => Vec::new(),
+            LocalizationLoadPolicy::KeepLoaded => self.handle_map.keys().cloned().collect(),
+            LocalizationLoadPolicy::PreloadAll => {
+                if self.available_locales.is_none() || self.layers_changed {
+                    self.available_locales = Some(self.find_available_locales(asset_server));
+                }
+
+                self.available_locales.clone().unwrap_or_default()
+            }
+            LocalizationLoadPolicy::Preload(locales) => locales.clone(),
+        }
+    }
+
+    /// Find the locales that any layer provides a localization file for.
+    ///
+    /// Listing the folders blocks, so this is only done once and when the layers change.
+    fn find_available_locales(&self, asset_server: &AssetServer) -> Vec<LanguageIdentifier> {
+        let mut locales = Vec::new();
+
+        for (layer, path) in self.layer_paths().enumerate() {
+            let file_names: Vec<String> = match self.embedded_sources {
+                Some(sources) if layer == 0 => {
+                    sources.iter().map(|(name, _)| name.to_string()).collect()
+                }
+                _ => match asset_server
+                    .asset_io()
+                    .read_directory(Path::new(asset_folder_path(path)))
+                {
+                    Ok(paths) => paths
+                        .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+                        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                        .collect(),
+                    Err(err) => {
+                        warn!("Failed to list the localization files of {path}: {err}");
+                        continue;
+                    }
+                },
+            };
+
+            for lang_id in file_names.iter().filter_map(|name| name.parse().ok()) {
+                if !locales.contains(&lang_id) {
+                    locales.push(lang_id);
+                }
+            }
+        }
+
+        locales
+    }
+
+    /// Use the given resolution chain for the localization.
+    ///
+    /// The localization files of locales that are new to the chain are loaded.
+    /// The previous chain stays active until they are loaded, see
+    /// [`FolderLocalization::update_pending_chain`].
+    /// Afterwards, the files of locales that are no longer part of it are dropped.
+    pub(crate) fn set_resolution_chain(
+        &mut self,
+        resolution_chain: Vec<LanguageIdentifier>,
+        asset_server: &AssetServer,
+        assets: &mut Assets<LocalizationSource>,
+    ) {
+        // Nothing can be shown yet, so there is nothing to keep
+        if resolution_chain == self.resolution_chain {
+            self.pending_chain = None;
+        } else if self.resolution_chain.is_empty() {
+            self.resolution_chain = resolution_chain;
+            self.pending_chain = None;
+            self.generations.mark_all_changed();
+        } else {
+            self.pending_chain = Some(PendingChain {
+                resolution_chain,
+                started: None,
+            });
+        }
+
+        self.load_sources(asset_server, assets);
+
+        // The files might already be loaded, e.g. because of the load policy
+        if self.is_pending_chain_ready(asset_server) {
+            self.finish_switch();
+        }
+    }
+
+    /// Switch to the pending resolution chain if its files are loaded or the timeout has elapsed.
+    ///
+    /// Without the current time, the timeout is skipped and the files are awaited.
+    ///
+    /// Returns `true` if the resolution chain has been switched.
+    pub(crate) fn update_pending_chain(
+        &mut self,
+        asset_server: &AssetServer,
+        now: Option<Duration>,
+        timeout: Duration,
+    ) -> bool {
+        let Some(pending) = &mut self.pending_chain else {
+            return false;
+        };
+        let elapsed = now.map(|now| now.saturating_sub(*pending.started.get_or_insert(now)));
+
+        if self.is_pending_chain_ready(asset_server) {
+            self.finish_switch();
+            true
+        } else if elapsed.is_some_and(|elapsed| elapsed >= timeout) {
+            warn!(
+                "Timed out loading the localization files of {}, switching the locale anyway",
+                self.folder_path
+            );
+            self.finish_switch();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Determine if the files of all locales in the pending resolution chain are loaded.
+    fn is_pending_chain_ready(&self, asset_server: &AssetServer) -> bool {
+        self.pending_resolution_chain().is_some_and(|chain| {
+            chain
+                .iter()
+                .all(|lang_id| self.is_ready(lang_id, asset_server))
+        })
+    }
+
+    /// Make the pending resolution chain the active one and drop the files no longer needed.
+    fn finish_switch(&mut self) {
+        if let Some(pending) = self.pending_chain.take() {
+            self.resolution_chain = pending.resolution_chain;
+            self.generations.mark_all_changed();
+        }
+
+        let loaded_locales: Vec<LanguageIdentifier> =
+            self.loaded_locales().into_iter().cloned().collect();
+        self.retain_locales(&loaded_locales);
+    }
+
+    /// Load the localization files of all layers for every loaded locale.
+    pub(crate) fn load_sources(
+        &mut self,
+        asset_server: &AssetServer,
+        assets: &mut Assets<LocalizationSource>,
+    ) {
+        self.preloaded_locales = self.policy_locales(asset_server);
+        let loaded_locales: Vec<LanguageIdentifier> =
+            self.loaded_locales().into_iter().cloned().collect();
+
+        for lang_id in &loaded_locales {
+            let handles: Vec<Handle<LocalizationSource>> = self
+                .layer_paths()
+                .enumerate()
+                .map(|(layer, path)| {
+                    let ftl_path = get_ftl_path(asset_folder_path(path), lang_id);
+
+                    match self.embedded_sources {
+                        Some(sources) if layer == 0 => {
+                            load_embedded_source(sources, &ftl_path, lang_id, assets)
+                        }
+                        _ => asset_server.load(ftl_path),
+                    }
+                })
+                .collect();
+
+            if self.handle_map.get(lang_id) != Some(&handles) {
+                // Some of the files might already be loaded, so they won't emit an asset event
+                let resources = handles
+                    .iter()
+                    .zip(self.layer_paths())
+                    .map(|(handle, path)| {
+                        parse_source(handle, assets, &get_ftl_path(path, lang_id))
+                    })
+                    .collect();
+
+                for (layer, handle) in handles.iter().enumerate() {
+                    self.source_index
+                        .insert(handle.id(), (lang_id.clone(), layer));
+                }
+
+                // Replaced handles are no longer part of the localization
+                if let Some(previous) = self.handle_map.insert(lang_id.clone(), handles) {
+                    let handles = &self.handle_map[lang_id];
+
+                    for handle in previous.iter().filter(|handle| !handles.contains(handle)) {
+                        self.source_index.remove(&handle.id());
+                    }
+                }
+                self.resource_map.insert(lang_id.clone(), resources);
+                self.rebuild_bundle(lang_id);
+                self.sources_changed = true;
+            } else if !self.bundle_map.contains_key(lang_id) {
+                // Inserted sources may need a bundle before any file is loaded
+                self.rebuild_bundle(lang_id);
+            }
+        }
+
+        self.retain_locales(&loaded_locales);
+        self.layers_changed = false;
+    }
+
+    /// Remove the handles and bundles of all other locales.
+    fn retain_locales(&mut self, locales: &[LanguageIdentifier]) {
+        let source_count = self.source_index.len();
+
+        self.handle_map
+            .retain(|lang_id, _| locales.contains(lang_id));
+        self.source_index
+            .retain(|_, (lang_id, _)| locales.contains(lang_id));
+        self.sources_changed |= self.source_index.len() != source_count;
+        self.resource_map
+            .retain(|lang_id, _| locales.contains(lang_id));
+        self.bundle_map
+            .retain(|lang_id, _| locales.contains(lang_id));
+        self.message_sources
+            .retain(|lang_id, _| locales.contains(lang_id));
+    }
+
+    /// Get the locale and layer that the given localization file has been loaded for.
+    fn position_of(
+        &self,
+        handle: &Handle<LocalizationSource>,
+    ) -> Option<(LanguageIdentifier, usize)> {
+        self.source_index.get(&handle.id()).cloned()
+    }
+
+    /// Iterate over the IDs of all localization files of this localization.
+    pub(crate) fn source_ids(&self) -> impl Iterator<Item = HandleId> + '_ {
+        self.source_index.keys().copied()
+    }
+
+    /// Update the bundles after a localization asset has been changed.
+    ///
+    /// Returns `true` if the messages of a locale that is currently looked up have changed.
+    pub(crate) fn handle_asset_event(
+        &mut self,
+        event: &AssetEvent<LocalizationSource>,
+        assets: &Assets<LocalizationSource>,
+    ) -> bool {
+        let (AssetEvent::Created { handle }
+        | AssetEvent::Modified { handle }
+        | AssetEvent::Removed { handle }) = event;
+
+        let Some((lang_id, layer)) = self.position_of(handle) else {
+            return false;
+        };
+
+        let resource = match event {
+            AssetEvent::Removed { .. } => None,
+            _ => {
+                let ftl_path = self
+                    .layer_paths()
+                    .nth(layer)
+                    .map(|path| get_ftl_path(path, &lang_id))
+                    .unwrap_or_default();
+                parse_source(handle, assets, &ftl_path)
+            }
+        };
+
+        if let Some(resources) = self.resource_map.get_mut(&lang_id) {
+            resources[layer] = resource;
+        }
+
+        self.rebuild_bundle(&lang_id);
+        self.is_active(&lang_id)
+    }
+
+    /// Merge the sources of all layers into the bundle of the given locale.
+    ///
+    /// Bundles are only kept for the locales in the resolution chain and the overridden locales.
+    fn rebuild_bundle(&mut self, lang_id: &LanguageIdentifier) {
+        if !self.is_loaded(lang_id) {
+            return;
+        }
+
+        let start = Instant::now();
+
+        // Not every layer has to provide a file for every locale
+        let file_resources = self
+            .resource_map
+            .get(lang_id)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .filter_map(|(layer, resource)| Some((layer, resource.as_ref()?)));
+        let inserted_resources = self
+            .inserted_sources
+            .get(lang_id)
+            .into_iter()
+            .flatten()
+            .map(|resource| (self.layers.len() + 1, resource));
+
+        let mut bundle = FluentBundle::new_concurrent(vec![lang_id.clone()]);
+        let mut message_sources = HashMap::default();
+        let mut is_empty = true;
+
+        for (layer, resource) in file_resources.chain(inserted_resources) {
+            for (index, entry) in resource.entries().enumerate() {
+                if let Some(id) = entry_id(entry) {
+                    message_sources.insert(id, MessageSource::new(layer, resource.clone(), index));
+                }
+            }
+
+            bundle.add_resource_overriding(resource.clone());
+            is_empty = false;
+        }
+
+        if self.is_active(lang_id) {
+            let changed = changed_messages(self.message_sources.get(lang_id), &message_sources);
+            self.generations.mark_changed(changed);
+        }
+
+        if is_empty {
+            self.bundle_map.remove(lang_id);
+            self.message_sources.remove(lang_id);
+        } else {
+            self.bundle_map.insert(lang_id.clone(), bundle);
+            self.message_sources
+                .insert(lang_id.clone(), message_sources);
+        }
+
+        self.metrics.record_build_time(start.elapsed());
+    }
+
+    /// Use the given locales for the locale overrides.
+    ///
+    /// The files are loaded with the next call to [`FolderLocalization::load_sources`].
+    pub(crate) fn set_override_locales(&mut self, override_locales: Vec<LanguageIdentifier>) {
+        if override_locales != self.override_locales {
+            self.override_locales = override_locales;
+            self.generations.mark_all_changed();
+        }
+    }
+
+    /// Cache the formatted messages, so that messages requested every frame are only
+    /// formatted once.
+    ///
+    /// Only messages resolved through the active resolution chain are cached.
+    /// A cached message is formatted again once it changes, e.g. because the locale changed
+    /// or its localization file has been reloaded.
+    /// When the cache holds `capacity` messages, the least recently used one is dropped.
+    pub fn enable_format_cache(&mut self, capacity: usize) {
+        self.format_cache = Some(FormatCache::new(capacity));
+    }
+
+    /// Stop caching formatted messages, dropping the cache.
+    pub fn disable_format_cache(&mut self) {
+        self.format_cache = None;
+    }
+
+    /// The statistics of the format cache, if it is enabled.
+    pub fn format_cache_stats(&self) -> Option<FormatCacheStats> {
+        self.format_cache.as_ref().map(FormatCache::stats)
+    }
+
+    fn try_format_pattern(
+        &self,
+        resolution_chain: &[LanguageIdentifier],
+        message_id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<String, LocalizationError> {
+        let mut format_errors = false;
+
+        for (position, lang_id) in resolution_chain.iter().enumerate() {
+            if let Some(bundle) = self.bundle_map.get(lang_id) {
+                if let Some(msg) = bundle.get_message(message_id) {
+                    let mut errors = vec![];
+
+                    if let Some(pattern) = msg.value() {
+                        let formatted_message = bundle.format_pattern(pattern, args, &mut errors);
+
+                        if errors.is_empty() {
+                            self.metrics.record_format(Some(position), format_errors);
+                            return Ok(formatted_message.to_string());
+                        }
+
+                        format_errors = true;
+                    }
+                }
+            }
+        }
+
+        self.metrics.record_format(None, format_errors);
+        Err(LocalizationError::MessageNotFound)
+    }
+
+    fn try_format(
+        &self,
+        resolution_chain: &[LanguageIdentifier],
+        message_id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<String, LocalizationError> {
+        let msg = self.try_format_pattern(resolution_chain, message_id, args)?;
+
+        if args.is_none() {
+            return Ok(msg);
+        }
+
+        // The inserted values are wrapped in U+2058 (First Strong Isolate) and U+2069 (Pop Directional Isolate)
+        // The font can't handle them, so we replace them for now
+        // TODO: Don't do this
+        Ok(msg.replace(['\u{2068}', '\u{2069}'], ""))
+    }
+
+    /// Format a message through the active resolution chain, using the format cache if enabled.
+    fn try_format_active(
+        &self,
+        message_id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<String, LocalizationError> {
+        let format = || self.try_format(&self.resolution_chain, message_id, args);
+
+        match &self.format_cache {
+            Some(cache) => cache.get_or_format(message_id, args, &self.generations, format),
+            None => format(),
+        }
+    }
+
+    pub fn try_get_message(&self, message_id: &str) -> Result<String, LocalizationError> {
+        self.try_format_active(message_id, None)
+    }
+
+    pub fn try_format_message(
+        &self,
+        message_id: &str,
+        args: FluentArgs,
+    ) -> Result<String, LocalizationError> {
+        self.try_format_active(message_id, Some(&args))
+    }
+
+    /// Format a message into the given buffer, replacing its contents.
+    ///
+    /// Unlike [`FolderLocalization::try_format_message`], this doesn't allocate a new string,
+    /// so the buffer can be reused, e.g. the value of a [`TextSection`].
+    /// The format cache is not used.
+    ///
+    /// If the message can't be formatted, the buffer is left empty.
+    pub fn format_into(
+        &self,
+        buf: &mut String,
+        message_id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<(), LocalizationError> {
+        buf.clear();
+
+        self.write_in(buf, &self.resolution_chain, message_id, args, |buf| {
+            buf.clear();
+            true
+        })
+    }
+
+    /// Write a message to the given writer.
+    ///
+    /// The message is written directly, without allocating an intermediate string.
+    /// The format cache is not used.
+    ///
+    /// Since the output can't be taken back, a message that fails to format is not looked up
+    /// in the next locale of the resolution chain, and the output may be incomplete.
+    pub fn write_message(
+        &self,
+        w: &mut impl fmt::Write,
+        message_id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<(), LocalizationError> {
+        self.write_in(w, &self.resolution_chain, message_id, args, |_| false)
+    }
+
+    /// Write a message through the given resolution chain.
+    ///
+    /// If formatting fails in a locale, `retry` is called to discard the partial output.
+    /// It returns whether the next locale of the chain should be tried.
+    fn write_in<W: fmt::Write>(
+        &self,
+        w: &mut W,
+        resolution_chain: &[LanguageIdentifier],
+        message_id: &str,
+        args: Option<&FluentArgs>,
+        mut retry: impl FnMut(&mut W) -> bool,
+    ) -> Result<(), LocalizationError> {
+        let mut format_errors = false;
+
+        for (position, lang_id) in resolution_chain.iter().enumerate() {
+            let Some(bundle) = self.bundle_map.get(lang_id) else {
+                continue;
+            };
+            let Some(pattern) = bundle.get_message(message_id).and_then(|msg| msg.value()) else {
+                continue;
+            };
+
+            let mut errors = vec![];
+
+            // See `FolderLocalization::try_format` for why the isolation marks are left out
+            if args.is_some() {
+                bundle.write_pattern(&mut WithoutIsolates(&mut *w), pattern, args, &mut errors)
+            } else {
+                bundle.write_pattern(w, pattern, args, &mut errors)
+            }
+            .map_err(LocalizationError::Write)?;
+
+            if errors.is_empty() {
+                self.metrics.record_format(Some(position), format_errors);
+                return Ok(());
+            }
+
+            format_errors = true;
+
+            if !retry(w) {
+                break;
+            }
+        }
+
+        self.metrics.record_format(None, format_errors);
+        Err(LocalizationError::MessageNotFound)
+    }
+
+    /// Get a message, resolved through the given resolution chain instead of the active one.
+    ///
+    /// Only locales that are loaded are considered, i.e. the locales of the active resolution
+    /// chain and of the [`LocaleOverride`](crate::locale::LocaleOverride) components.
+    pub fn try_get_message_in(
+        &self,
+        resolution_chain: &[LanguageIdentifier],
+        message_id: &str,
+    ) -> Result<String, LocalizationError> {
+        self.try_format(resolution_chain, message_id, None)
+    }
+
+    /// Format a message, resolved through the given resolution chain instead of the active one.
+    ///
+    /// See [`FolderLocalization::try_get_message_in`] for the locales that are considered.
+    pub fn try_format_message_in(
+        &self,
+        resolution_chain: &[LanguageIdentifier],
+        message_id: &str,
+        args: FluentArgs,
+    ) -> Result<String, LocalizationError> {
+        self.try_format(resolution_chain, message_id, Some(&args))
+    }
+}
+
+/// Writes to the inner writer, leaving out the Unicode isolation marks around placeables.
+struct WithoutIsolates<'w, W>(&'w mut W);
+
+impl<W: fmt::Write> fmt::Write for WithoutIsolates<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for part in s.split(['\u{2068}', '\u{2069}']) {
+            self.0.write_str(part)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse the given localization file, if it is loaded.
+///
+/// Entries with syntax errors are skipped with a warning, so that a typo in a hot-reloaded
+/// file doesn't remove all messages of the locale.
+fn parse_source(
+    handle: &Handle<LocalizationSource>,
+    assets: &Assets<LocalizationSource>,
+    ftl_path: &Path,
+) -> Option<Arc<FluentResource>> {
+    let source = assets.get(handle)?;
+
+    // TODO: Make this more efficient, the parsing could take some time
+    let resource =
+        FluentResource::try_new(source.ftl_string.clone()).unwrap_or_else(|(resource, errors)| {
+            let first_error = errors.first().map(ToString::to_string).unwrap_or_default();
+            warn!(
+                "Skipped {} invalid entries in {}: {first_error}",
+                errors.len(),
+                ftl_path.display()
+            );
+            resource
+        });
+
+    Some(Arc::new(resource))
+}
+
+/// Add the embedded localization file for the given locale to the assets.
+///
+/// The asset is identified by its would-be path, so it is only added once.
+/// If the locale has no embedded file, the returned handle won't point to an asset.
+fn load_embedded_source(
+    sources: &[(&str, &str)],
+    ftl_path: &Path,
+    lang_id: &LanguageIdentifier,
+    assets: &mut Assets<LocalizationSource>,
+) -> Handle<LocalizationSource> {
+    let id = HandleId::from(format!("embedded://{}", ftl_path.display()));
+
+    if !assets.contains(&Handle::weak(id)) {
+        let source = sources
+            .iter()
+            .find(|(name, _)| name.parse::<LanguageIdentifier>().ok().as_ref() == Some(lang_id));
+
+        if let Some((_, ftl_string)) = source {
+            assets.set_untracked(id, LocalizationSource::new(ftl_string.to_string()));
+        }
+    }
+
+    assets.get_handle(id)
+}
@@ -0,0 +1,233 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use bevy::{prelude::*, utils::HashMap};
+use unic_langid::{subtags::Script, LanguageIdentifier};
+
+use super::utils::get_resolution_chain;
+use crate::locale::{
+    IntoLanguageIdentifier, InvalidLocaleError, Locale, LocaleDefaultFallback, LocaleFallbackMap,
+    LocaleOverride, TryIntoLanguageIdentifier,
+};
+
+/// The fonts to use for localized text, depending on the active locale.
+///
+/// Many fonts only support a limited set of characters, so different languages
+/// might need different fonts.
+/// Fonts can be defined for specific locales or for the script a locale is written in.
+/// The font is determined by following the resolution chain, i.e. the fallbacks of the
+/// [`Locale`] are considered if no font is defined for the locale itself.
+///
+/// When this resource is inserted, the font of [`LocalizedText`](super::LocalizedText)
+/// and [`DynamicLocalizedText`](super::DynamicLocalizedText) is updated together with the text,
+/// i.e. once the localization files of a new locale are loaded.
+/// The fonts are loaded on demand.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// let mut localized_font = LocalizedFont::new();
+/// localized_font.set_default("fonts/FiraSans-Bold.ttf");
+/// localized_font
+///     .try_insert_script("Arab", "fonts/NotoSansArabic-Bold.ttf")
+///     .unwrap();
+/// localized_font.insert_locale("ja", "fonts/NotoSansJP-Bold.ttf");
+///
+/// App::new()
+///     .insert_resource(Locale::new("en-US"))
+///     .insert_resource(localized_font)
+///     .add_plugin(LocalizationPlugin)
+///     // -- snip --
+///     .run();
+/// ```
+#[derive(Debug, Default, Resource)]
+pub struct LocalizedFont {
+    locale_fonts: HashMap<LanguageIdentifier, String>,
+    script_fonts: HashMap<Script, String>,
+    default_font: Option<String>,
+    /// The handles of all fonts that have been used so far.
+    handles: HashMap<String, Handle<Font>>,
+    /// The path of the font for the active locale.
+    active_font: Option<String>,
+}
+
+impl LocalizedFont {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the font to use if no font is defined for any locale in the resolution chain.
+    pub fn set_default(&mut self, font_path: impl Into<String>) {
+        self.default_font = Some(font_path.into());
+    }
+
+    /// Set the font for the given locale.
+    ///
+    /// A font defined for a language without region (e.g. `ja`) is also used for
+    /// all regional variants of that language (e.g. `ja-JP`).
+    pub fn insert_locale<T: IntoLanguageIdentifier>(
+        &mut self,
+        locale: T,
+        font_path: impl Into<String>,
+    ) -> Option<String> {
+        self.locale_fonts
+            .insert(locale.into_language_identifier(), font_path.into())
+    }
+
+    /// Set the font for the given locale.
+    ///
+    /// If the locale is not a valid language tag, an error is returned and nothing is inserted.
+    pub fn try_insert_locale<T: TryIntoLanguageIdentifier>(
+        &mut self,
+        locale: T,
+        font_path: impl Into<String>,
+    ) -> Result<Option<String>, InvalidLocaleError> {
+        Ok(self
+            .locale_fonts
+            .insert(locale.try_into_language_identifier()?, font_path.into()))
+    }
+
+    /// Set the font for all locales written in the given script.
+    ///
+    /// If a locale doesn't specify a script, its most likely script is used.
+    pub fn insert_script(
+        &mut self,
+        script: Script,
+        font_path: impl Into<String>,
+    ) -> Option<String> {
+        self.script_fonts.insert(script, font_path.into())
+    }
+
+    /// Set the font for all locales written in the given script.
+    ///
+    /// The script must be a valid ISO 15924 code, e.g. `Latn`, `Cyrl`, `Arab` or `Hans`.
+    /// Otherwise, an error is returned and nothing is inserted.
+    ///
+    /// ```
+    /// # use bevy_mod_localization::localization::LocalizedFont;
+    /// #
+    /// let mut localized_font = LocalizedFont::new();
+    ///
+    /// assert!(localized_font.try_insert_script("Cyrl", "fonts/Cyrillic.ttf").is_ok());
+    ///
+    /// let err = localized_font.try_insert_script("Cyrillic", "fonts/Cyrillic.ttf").unwrap_err();
+    /// assert_eq!(err.script(), "Cyrillic");
+    /// ```
+    pub fn try_insert_script(
+        &mut self,
+        script: &str,
+        font_path: impl Into<String>,
+    ) -> Result<Option<String>, InvalidScriptError> {
+        let script = Script::from_str(script).map_err(|_| InvalidScriptError {
+            script: script.to_string(),
+        })?;
+
+        Ok(self.insert_script(script, font_path))
+    }
+
+    /// The font for the active locale, if any.
+    pub fn font(&self) -> Option<&Handle<Font>> {
+        self.handles.get(self.active_font.as_ref()?)
+    }
+
+    /// The font for the given resolution chain, if it has been loaded.
+    ///
+    /// The fonts of the [`LocaleOverride`] components are loaded automatically.
+    pub fn font_for(&self, resolution_chain: &[LanguageIdentifier]) -> Option<&Handle<Font>> {
+        self.handles.get(self.resolve(resolution_chain)?)
+    }
+
+    /// Determine the font path for the given resolution chain.
+    fn resolve(&self, resolution_chain: &[LanguageIdentifier]) -> Option<&String> {
+        resolution_chain
+            .iter()
+            .find_map(|lang_id| {
+                let language = LanguageIdentifier::from_parts(lang_id.language, None, None, &[]);
+
+                let mut maximized = lang_id.clone();
+                maximized.maximize();
+
+                self.locale_fonts
+                    .get(lang_id)
+                    .or_else(|| self.locale_fonts.get(&language))
+                    .or_else(|| self.script_fonts.get(&maximized.script?))
+            })
+            .or(self.default_font.as_ref())
+    }
+}
+
+/// A string is not a valid ISO 15924 script code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidScriptError {
+    script: String,
+}
+
+impl InvalidScriptError {
+    /// The string that failed to parse.
+    pub fn script(&self) -> &str {
+        &self.script
+    }
+}
+
+impl fmt::Display for InvalidScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid script `{}`", self.script)
+    }
+}
+
+impl Error for InvalidScriptError {}
+
+/// Determine the font for the active locale and load it, if needed.
+///
+/// The fonts for the [`LocaleOverride`] components are loaded as well.
+pub fn update_localized_font(
+    mut localized_font: ResMut<LocalizedFont>,
+    asset_server: Res<AssetServer>,
+    locale: Res<Locale>,
+    fallback_map: Res<LocaleFallbackMap>,
+    default_fallback: Res<LocaleDefaultFallback>,
+    overrides: Query<Ref<LocaleOverride>>,
+) {
+    let font_changed = localized_font.is_changed();
+
+    for locale_override in overrides.iter() {
+        if !(font_changed || locale_override.is_changed()) {
+            continue;
+        }
+
+        let font_path = localized_font
+            .resolve(locale_override.resolution_chain())
+            .filter(|font_path| !localized_font.handles.contains_key(*font_path))
+            .cloned();
+
+        if let Some(font_path) = font_path {
+            let handle = asset_server.load(font_path.as_str());
+            localized_font.handles.insert(font_path, handle);
+        }
+    }
+
+    if !(font_changed
+        || locale.is_changed()
+        || fallback_map.is_changed()
+        || default_fallback.is_changed())
+    {
+        return;
+    }
+
+    let resolution_chain = get_resolution_chain(&locale.0, &fallback_map, &default_fallback);
+    let font_path = localized_font.resolve(&resolution_chain).cloned();
+
+    // Avoid triggering change detection if nothing changed
+    if font_path == localized_font.active_font {
+        return;
+    }
+
+    if let Some(font_path) = &font_path {
+        if !localized_font.handles.contains_key(font_path) {
+            let handle = asset_server.load(font_path.as_str());
+            localized_font.handles.insert(font_path.clone(), handle);
+        }
+    }
+
+    localized_font.active_font = font_path;
+}
@@ -0,0 +1,277 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, PoisonError,
+    },
+};
+
+use bevy::utils::HashMap;
+use fluent::{types::FluentNumberOptions, FluentArgs, FluentValue};
+
+use super::message_changes::MessageGenerations;
+use crate::LocalizationError;
+
+/// Statistics of the formatted-message cache of a localization.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FormatCacheStats {
+    /// The number of messages that have been taken from the cache.
+    pub hits: u64,
+    /// The number of messages that had to be formatted.
+    pub misses: u64,
+    /// The number of messages currently in the cache.
+    pub entries: usize,
+}
+
+/// An argument value of a cached message, copied to compare it with the requested arguments.
+#[derive(Debug, PartialEq)]
+enum CachedArg {
+    String(String),
+    /// The bits of the number, together with its formatting options.
+    Number(u64, FluentNumberOptions),
+    None,
+    Error,
+}
+
+impl CachedArg {
+    /// Copy the argument value. Custom values can't be compared, so they return `None`.
+    fn new(value: &FluentValue) -> Option<Self> {
+        match value {
+            FluentValue::String(string) => Some(Self::String(string.to_string())),
+            FluentValue::Number(number) => {
+                Some(Self::Number(number.value.to_bits(), number.options.clone()))
+            }
+            FluentValue::None => Some(Self::None),
+            FluentValue::Error => Some(Self::Error),
+            FluentValue::Custom(_) => None,
+        }
+    }
+
+    /// Determine if the argument value is equal to the given one, without copying it.
+    fn matches(&self, value: &FluentValue) -> bool {
+        match (self, value) {
+            (Self::String(cached), FluentValue::String(string)) => cached == string,
+            (Self::Number(bits, options), FluentValue::Number(number)) => {
+                *bits == number.value.to_bits() && *options == number.options
+            }
+            (Self::None, FluentValue::None) | (Self::Error, FluentValue::Error) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A formatted message, together with the generation it has been formatted in.
+struct CachedMessage {
+    message_id: String,
+    /// The arguments, sorted by name like in [`FluentArgs`].
+    args: Vec<(String, CachedArg)>,
+    generation: u64,
+    /// When the message has last been used, to evict the least recently used messages.
+    last_used: u64,
+    /// The formatted message, or `None` if formatting failed.
+    message: Option<String>,
+}
+
+impl CachedMessage {
+    /// Determine if this is the message for the given ID and arguments.
+    ///
+    /// The cache is keyed by a hash, so the message and arguments have to be compared as well.
+    fn is_for(&self, message_id: &str, args: Option<&FluentArgs>) -> bool {
+        let mut args = args.into_iter().flat_map(FluentArgs::iter);
+
+        self.message_id == message_id
+            && self.args.iter().all(|(cached_name, cached_value)| {
+                args.next()
+                    .is_some_and(|(name, value)| cached_name == name && cached_value.matches(value))
+            })
+            && args.next().is_none()
+    }
+}
+
+struct CacheEntries {
+    /// The generation in which the entries have last been cleared.
+    generation: u64,
+    /// The cached messages, keyed by the hash of the message ID and arguments.
+    messages: HashMap<u64, CachedMessage>,
+    /// The hashes of the cached messages, by when they have last been used.
+    recently_used: BTreeMap<u64, u64>,
+    /// Counts the uses of the cache, to order the messages by their last use.
+    uses: u64,
+}
+
+/// Memoizes formatted messages, so that messages requested every frame are only formatted once.
+///
+/// An entry is invalidated when its message changes, see [`MessageGenerations`].
+/// When the cache is full, the least recently used entry is evicted.
+pub(crate) struct FormatCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    /// The hits and misses since they have last been measured for the diagnostics.
+    unmeasured_hits: AtomicU64,
+    unmeasured_misses: AtomicU64,
+}
+
+impl FormatCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(CacheEntries {
+                generation: 0,
+                messages: HashMap::default(),
+                recently_used: BTreeMap::new(),
+                uses: 0,
+            }),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            unmeasured_hits: AtomicU64::new(0),
+            unmeasured_misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the formatted message from the cache, or format and cache it.
+    ///
+    /// Messages with custom argument values can't be compared and are always formatted.
+    pub(crate) fn get_or_format(
+        &self,
+        message_id: &str,
+        args: Option<&FluentArgs>,
+        generations: &MessageGenerations,
+        format: impl FnOnce() -> Result<String, LocalizationError>,
+    ) -> Result<String, LocalizationError> {
+        let Some(key) = cache_key(message_id, args) else {
+            self.record(false);
+            return format();
+        };
+
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let CacheEntries {
+            generation,
+            messages,
+            recently_used,
+            uses,
+        } = &mut *entries;
+
+        // Drop all entries at once if all of them are outdated anyway
+        if generations.is_all_changed_since(*generation) {
+            messages.clear();
+            recently_used.clear();
+            *generation = generations.current();
+        }
+
+        *uses += 1;
+
+        if let Some(cached) = messages.get_mut(&key) {
+            if cached.is_for(message_id, args)
+                && !generations.is_changed_since(message_id, cached.generation)
+            {
+                self.record(true);
+
+                recently_used.remove(&cached.last_used);
+                recently_used.insert(*uses, key);
+                cached.last_used = *uses;
+
+                return cached
+                    .message
+                    .clone()
+                    .ok_or(LocalizationError::MessageNotFound);
+            }
+        }
+
+        self.record(false);
+        let message = format();
+
+        if let Some(replaced) = messages.remove(&key) {
+            // The entry is outdated or belongs to another message with the same hash
+            recently_used.remove(&replaced.last_used);
+        } else {
+            while messages.len() >= self.capacity.max(1) {
+                let Some((_, evicted)) = recently_used.pop_first() else {
+                    break;
+                };
+                messages.remove(&evicted);
+            }
+        }
+
+        recently_used.insert(*uses, key);
+        messages.insert(
+            key,
+            CachedMessage {
+                message_id: message_id.to_string(),
+                args: cached_args(args),
+                generation: generations.current(),
+                last_used: *uses,
+                message: message.as_ref().ok().cloned(),
+            },
+        );
+
+        message
+    }
+    pub(crate) fn stats(&self) -> FormatCacheStats {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+
+        FormatCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: entries.messages.len(),
+        }
+    }
+
+    /// Take the number of hits and misses since the last call.
+    pub(crate) fn take_measurements(&self) -> (u64, u64) {
+        (
+            self.unmeasured_hits.swap(0, Ordering::Relaxed),
+            self.unmeasured_misses.swap(0, Ordering::Relaxed),
+        )
+    }
+
+    fn record(&self, hit: bool) {
+        let (total, unmeasured) = if hit {
+            (&self.hits, &self.unmeasured_hits)
+        } else {
+            (&self.misses, &self.unmeasured_misses)
+        };
+
+        total.fetch_add(1, Ordering::Relaxed);
+        unmeasured.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Hash the message ID and arguments of a message.
+///
+/// Returns `None` if the arguments contain custom values, which can't be compared.
+fn cache_key(message_id: &str, args: Option<&FluentArgs>) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    message_id.hash(&mut hasher);
+
+    for (name, value) in args.into_iter().flat_map(FluentArgs::iter) {
+        name.hash(&mut hasher);
+
+        match value {
+            FluentValue::String(string) => {
+                0u8.hash(&mut hasher);
+                string.hash(&mut hasher);
+            }
+            FluentValue::Number(number) => {
+                1u8.hash(&mut hasher);
+                number.value.to_bits().hash(&mut hasher);
+                number.options.hash(&mut hasher);
+            }
+            FluentValue::None => 2u8.hash(&mut hasher),
+            FluentValue::Error => 3u8.hash(&mut hasher),
+            FluentValue::Custom(_) => return None,
+        }
+    }
+
+    Some(hasher.finish())
+}
+
+/// Copy the arguments of a message to cache them.
+fn cached_args(args: Option<&FluentArgs>) -> Vec<(String, CachedArg)> {
+    args.into_iter()
+        .flat_map(FluentArgs::iter)
+        .filter_map(|(name, value)| Some((name.to_string(), CachedArg::new(value)?)))
+        .collect()
+}
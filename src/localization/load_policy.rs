@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use unic_langid::LanguageIdentifier;
+
+/// Which locales the localizations keep loaded, besides the ones in the resolution chain.
+///
+/// By default, only the locales of the resolution chain are loaded, so switching the locale
+/// shows no text until the localization files of the new locale are loaded.
+/// Keeping more locales loaded makes switching instantaneous, at the cost of memory.
+///
+/// The policy applies to all localizations.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// App::new()
+///     .insert_resource(Locale::new("en-US"))
+///     .insert_resource(LocalizationLoadPolicy::PreloadAll)
+///     .add_plugin(LocalizationPlugin)
+///     // -- snip --
+///     .run();
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Resource)]
+pub enum LocalizationLoadPolicy {
+    /// Only keep the locales of the resolution chain loaded.
+    #[default]
+    OnlyChain,
+    /// Keep every locale loaded once it has been part of the resolution chain.
+    KeepLoaded,
+    /// Load all locales that the localization folders provide files for.
+    ///
+    /// The files are discovered by listing the folders once, which is not supported on every
+    /// platform, e.g. on the web. Files added later are only discovered when the layers of the
+    /// folder change. Embedded localization files are always discovered.
+    PreloadAll,
+    /// Load the given locales in addition to the resolution chain.
+    Preload(Vec<LanguageIdentifier>),
+}
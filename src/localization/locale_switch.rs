@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// How long to wait for the localization files of a new locale before switching to it anyway.
+///
+/// When the locale changes, the localizations keep using the previous resolution chain until
+/// the files of all locales in the new chain are loaded, so the text doesn't go blank or
+/// briefly show the default fallback.
+/// If loading takes longer than the timeout, the new chain is used regardless.
+/// The timeout is measured with the [`Time`] resource; without it, the files are awaited.
+///
+/// The font of [`LocalizedFont`](super::LocalizedFont) and the
+/// [`LocaleDirection`](crate::locale::LocaleDirection) switch together with the text.
+///
+/// The default timeout is two seconds.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// App::new()
+///     .insert_resource(Locale::new("en-US"))
+///     .insert_resource(LocaleSwitchTimeout::new(Duration::from_millis(500)))
+///     .add_plugin(LocalizationPlugin)
+///     // -- snip --
+///     .run();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct LocaleSwitchTimeout(pub(crate) Duration);
+
+impl LocaleSwitchTimeout {
+    pub fn new(timeout: Duration) -> Self {
+        Self(timeout)
+    }
+
+    /// Get the timeout.
+    pub fn get(&self) -> Duration {
+        self.0
+    }
+
+    /// Change the timeout.
+    pub fn set(&mut self, timeout: Duration) {
+        self.0 = timeout;
+    }
+}
+
+impl Default for LocaleSwitchTimeout {
+    fn default() -> Self {
+        Self(Duration::from_secs(2))
+    }
+}
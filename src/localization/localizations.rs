@@ -0,0 +1,127 @@
+use bevy::{prelude::*, utils::HashMap};
+use fluent::FluentArgs;
+
+use super::FolderLocalization;
+use crate::LocalizationError;
+
+/// Localization folders that are only known at runtime.
+///
+/// The [`Localization`](super::Localization) resource requires a type implementing
+/// [`LocalizationFolder`](super::LocalizationFolder), which is not possible for folders that
+/// are discovered while the game is running, e.g. the strings of mods or DLCs.
+/// These folders can be registered here instead, identified by their path.
+///
+/// The folders follow the current [`Locale`](crate::locale::Locale) and are hot-reloaded
+/// just like the typed localizations.
+/// Use [`DynamicLocalizedText`](super::DynamicLocalizedText) to localize [`Text`] components.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// fn load_mod_strings(mut localizations: ResMut<Localizations>) {
+///     // Load the strings from the folder `/assets/mods/example/strings`
+///     localizations.add_folder("mods/example/strings");
+/// }
+///
+/// fn print_mod_message(localizations: Res<Localizations>) {
+///     if let Ok(msg) = localizations.try_get_message("mods/example/strings", "hello") {
+///         println!("{msg}");
+///     }
+/// }
+/// ```
+#[derive(Default, Resource)]
+pub struct Localizations {
+    pub(crate) folders: HashMap<String, FolderLocalization>,
+}
+
+impl Localizations {
+    /// Add a localization folder.
+    ///
+    /// The path works like an asset path, i.e. by default, `assets` is the root of the path.
+    /// The localization files are loaded in the next update.
+    ///
+    /// Returns `false` if the folder had already been added.
+    pub fn add_folder(&mut self, folder_path: impl Into<String>) -> bool {
+        let folder_path = folder_path.into();
+
+        if self.folders.contains_key(&folder_path) {
+            return false;
+        }
+
+        self.folders
+            .insert(folder_path.clone(), FolderLocalization::new(folder_path));
+        true
+    }
+
+    /// Add a localization folder with files that are embedded into the binary.
+    ///
+    /// The sources are pairs of language ID and FTL string, e.g. created with [`include_str!`].
+    /// They replace the files of the folder in the asset folder.
+    ///
+    /// Returns `false` if the folder had already been added.
+    pub fn add_embedded_folder(
+        &mut self,
+        folder_path: impl Into<String>,
+        sources: &'static [(&'static str, &'static str)],
+    ) -> bool {
+        let folder_path = folder_path.into();
+
+        if self.folders.contains_key(&folder_path) {
+            return false;
+        }
+
+        let mut folder = FolderLocalization::new(folder_path.clone());
+        folder.embedded_sources = Some(sources);
+        self.folders.insert(folder_path, folder);
+        true
+    }
+
+    /// Remove a localization folder, unloading its localization files.
+    ///
+    /// Returns `false` if the folder had not been added.
+    pub fn remove_folder(&mut self, folder_path: &str) -> bool {
+        self.folders.remove(folder_path).is_some()
+    }
+
+    /// Determine if the given folder has been added.
+    pub fn contains_folder(&self, folder_path: &str) -> bool {
+        self.folders.contains_key(folder_path)
+    }
+
+    /// Iterate over the paths of all added folders.
+    pub fn folder_paths(&self) -> impl Iterator<Item = &str> {
+        self.folders.keys().map(String::as_str)
+    }
+
+    /// Get the localization of the given folder.
+    pub fn get(&self, folder_path: &str) -> Option<&FolderLocalization> {
+        self.folders.get(folder_path)
+    }
+
+    /// Get the localization of the given folder mutably, e.g. to add layers to it.
+    pub fn get_mut(&mut self, folder_path: &str) -> Option<&mut FolderLocalization> {
+        self.folders.get_mut(folder_path)
+    }
+
+    pub fn try_get_message(
+        &self,
+        folder_path: &str,
+        message_id: &str,
+    ) -> Result<String, LocalizationError> {
+        self.get(folder_path)
+            .ok_or(LocalizationError::FolderNotFound)?
+            .try_get_message(message_id)
+    }
+
+    pub fn try_format_message(
+        &self,
+        folder_path: &str,
+        message_id: &str,
+        args: FluentArgs,
+    ) -> Result<String, LocalizationError> {
+        self.get(folder_path)
+            .ok_or(LocalizationError::FolderNotFound)?
+            .try_format_message(message_id, args)
+    }
+}
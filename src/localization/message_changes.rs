@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use bevy::utils::{HashMap, HashSet};
+use fluent::FluentResource;
+use fluent_syntax::ast;
+
+use crate::fluent_visitor::{walk_entry, PatternVisitor};
+
+/// The entry that a message or term of a bundle has been taken from.
+pub(crate) struct MessageSource {
+    /// The index of the layer the entry is defined in.
+    pub(crate) layer: usize,
+    resource: Arc<FluentResource>,
+    /// The index of the entry in the resource.
+    entry: usize,
+}
+
+impl MessageSource {
+    pub(crate) fn new(layer: usize, resource: Arc<FluentResource>, entry: usize) -> Self {
+        Self {
+            layer,
+            resource,
+            entry,
+        }
+    }
+
+    fn entry(&self) -> Option<&ast::Entry<&str>> {
+        self.resource.get_entry(self.entry)
+    }
+}
+
+/// Get the ID of a message or term entry. Terms are prefixed with a `-`.
+pub(crate) fn entry_id(entry: &ast::Entry<&str>) -> Option<String> {
+    match entry {
+        ast::Entry::Message(message) => Some(message.id.name.to_string()),
+        ast::Entry::Term(term) => Some(format!("-{}", term.id.name)),
+        _ => None,
+    }
+}
+
+/// Determine which messages and terms differ between two versions of a bundle.
+///
+/// Messages referencing a changed message or term are considered changed as well.
+pub(crate) fn changed_messages(
+    old: Option<&HashMap<String, MessageSource>>,
+    new: &HashMap<String, MessageSource>,
+) -> HashSet<String> {
+    let empty = HashMap::default();
+    let old = old.unwrap_or(&empty);
+
+    let mut changed: HashSet<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|id| {
+            let old_entry = old.get(*id).and_then(MessageSource::entry);
+            let new_entry = new.get(*id).and_then(MessageSource::entry);
+            old_entry != new_entry
+        })
+        .cloned()
+        .collect();
+
+    if changed.is_empty() {
+        return changed;
+    }
+
+    let references: Vec<(&String, Vec<String>)> = new
+        .iter()
+        .map(|(id, source)| (id, source.entry().map(entry_references).unwrap_or_default()))
+        .collect();
+
+    // Propagate the changes until no more references are affected
+    loop {
+        let affected: Vec<String> = references
+            .iter()
+            .filter(|(id, references)| {
+                !changed.contains(*id) && references.iter().any(|id| changed.contains(id))
+            })
+            .map(|(id, _)| (*id).clone())
+            .collect();
+
+        if affected.is_empty() {
+            return changed;
+        }
+
+        changed.extend(affected);
+    }
+}
+
+/// Keeps track of which messages changed, so that only the affected text has to be updated.
+///
+/// Every change increments the generation.
+/// Readers remember the generation they have last seen and ask for the changes since then.
+#[derive(Debug, Default)]
+pub(crate) struct MessageGenerations {
+    current: u64,
+    /// The generation in which all messages have last been changed.
+    all_messages: u64,
+    /// The generation in which each message has last been changed, if after `all_messages`.
+    messages: HashMap<String, u64>,
+}
+
+impl MessageGenerations {
+    /// The current generation.
+    pub(crate) fn current(&self) -> u64 {
+        self.current
+    }
+
+    /// Record that all messages may have changed, e.g. because the resolution chain changed.
+    pub(crate) fn mark_all_changed(&mut self) {
+        self.current += 1;
+        self.all_messages = self.current;
+        self.messages.clear();
+    }
+
+    /// Record that the given messages have changed.
+    pub(crate) fn mark_changed(&mut self, message_ids: impl IntoIterator<Item = String>) {
+        let mut message_ids = message_ids.into_iter().peekable();
+
+        if message_ids.peek().is_none() {
+            return;
+        }
+
+        self.current += 1;
+
+        for message_id in message_ids {
+            self.messages.insert(message_id, self.current);
+        }
+    }
+
+    /// Determine if all messages may have changed after the given generation.
+    pub(crate) fn is_all_changed_since(&self, generation: u64) -> bool {
+        self.all_messages > generation
+    }
+
+    /// Determine if the given message may have changed after the given generation.
+    pub(crate) fn is_changed_since(&self, message_id: &str, generation: u64) -> bool {
+        self.is_all_changed_since(generation)
+            || self
+                .messages
+                .get(message_id)
+                .is_some_and(|changed| *changed > generation)
+    }
+}
+
+/// Get the IDs of all messages and terms referenced by the entry.
+fn entry_references(entry: &ast::Entry<&str>) -> Vec<String> {
+    let mut references = ReferenceCollector(Vec::new());
+    walk_entry(entry, &mut references);
+    references.0
+}
+
+/// Collects the IDs of the referenced messages and terms. Terms are prefixed with a `-`.
+struct ReferenceCollector(Vec<String>);
+
+impl<'s> PatternVisitor<'s> for ReferenceCollector {
+    fn visit_inline_expression(&mut self, expression: &ast::InlineExpression<&'s str>) {
+        match expression {
+            ast::InlineExpression::MessageReference { id, .. } => self.0.push(id.name.to_string()),
+            ast::InlineExpression::TermReference { id, .. } => self.0.push(format!("-{}", id.name)),
+            _ => {}
+        }
+    }
+}
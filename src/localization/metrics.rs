@@ -0,0 +1,72 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Counts the work done by a localization since the counters have last been taken.
+///
+/// The counters are taken every frame by the
+/// [`LocalizationDiagnosticsPlugin`](crate::diagnostics::LocalizationDiagnosticsPlugin).
+#[derive(Debug, Default)]
+pub(crate) struct LocalizationMetrics {
+    format_calls: AtomicU64,
+    fallback_hits: AtomicU64,
+    format_errors: AtomicU64,
+    failures: AtomicU64,
+    /// The time spent building bundles, in nanoseconds.
+    build_time: AtomicU64,
+}
+
+/// The work done by a localization since the counters have last been taken.
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct MetricsMeasurement {
+    pub(crate) format_calls: u64,
+    pub(crate) fallback_hits: u64,
+    pub(crate) format_errors: u64,
+    pub(crate) failures: u64,
+    pub(crate) build_time: Duration,
+}
+
+impl LocalizationMetrics {
+    /// Record that a message has been formatted.
+    ///
+    /// The position is the index of the locale in the resolution chain that the message has been
+    /// taken from, or `None` if the message couldn't be formatted in any locale.
+    /// A message that has been found, but had errors in a preceding locale is counted as
+    /// format error instead of fallback hit.
+    pub(crate) fn record_format(&self, position: Option<usize>, format_errors: bool) {
+        self.format_calls.fetch_add(1, Ordering::Relaxed);
+
+        if format_errors {
+            self.format_errors.fetch_add(1, Ordering::Relaxed);
+        }
+
+        match position {
+            Some(0) => {}
+            Some(_) if !format_errors => {
+                self.fallback_hits.fetch_add(1, Ordering::Relaxed);
+            }
+            Some(_) => {}
+            None => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Record the time it took to build a bundle.
+    pub(crate) fn record_build_time(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        self.build_time.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    /// Take the counters, resetting them.
+    pub(crate) fn take(&self) -> MetricsMeasurement {
+        MetricsMeasurement {
+            format_calls: self.format_calls.swap(0, Ordering::Relaxed),
+            fallback_hits: self.fallback_hits.swap(0, Ordering::Relaxed),
+            format_errors: self.format_errors.swap(0, Ordering::Relaxed),
+            failures: self.failures.swap(0, Ordering::Relaxed),
+            build_time: Duration::from_nanos(self.build_time.swap(0, Ordering::Relaxed)),
+        }
+    }
+}
@@ -1,86 +1,302 @@
 mod add_localization;
 mod components;
 mod folder;
-mod systems;
-mod utils;
+mod folder_localization;
+pub(crate) mod font;
+mod format_cache;
+mod load_policy;
+mod locale_switch;
+mod localizations;
+pub(crate) mod message_changes;
+mod metrics;
+pub(crate) mod source_index;
+pub(crate) mod systems;
+pub(crate) mod utils;
 
 pub use add_localization::AddLocalization;
-pub use components::LocalizedText;
+pub use components::{DynamicLocalizedText, LocalizedText};
 pub use folder::LocalizationFolder;
+pub use folder_localization::{FolderLocalization, MessageLayer};
+pub use font::{InvalidScriptError, LocalizedFont};
+pub use format_cache::FormatCacheStats;
+pub use load_policy::LocalizationLoadPolicy;
+pub use locale_switch::LocaleSwitchTimeout;
+pub use localizations::Localizations;
 
-use std::marker::PhantomData;
+use std::{fmt, marker::PhantomData};
 
-use crate::{fluent::FluentBundle, LocalizationSource};
-use bevy::{prelude::*, utils::HashMap};
+use bevy::prelude::*;
 use fluent::FluentArgs;
 use unic_langid::LanguageIdentifier;
 
-use crate::LocalizationError;
+use crate::{glyphs::MissingGlyphs, locale::TryIntoLanguageIdentifier, LocalizationError};
 
 #[derive(Resource)]
 pub struct Localization<T: LocalizationFolder> {
     phantom: std::marker::PhantomData<T>,
-    pub(crate) handle_map: HashMap<LanguageIdentifier, Handle<LocalizationSource>>,
-    pub(crate) bundle_map: HashMap<LanguageIdentifier, FluentBundle>,
-    pub(crate) resolution_chain: Vec<LanguageIdentifier>,
+    pub(crate) folder: FolderLocalization,
 }
 
 impl<T: LocalizationFolder> Localization<T> {
     pub fn new(resolution_chain: Vec<LanguageIdentifier>) -> Self {
-        Self {
-            resolution_chain,
-            ..Default::default()
-        }
+        let mut localization = Self::default();
+        localization.folder.resolution_chain = resolution_chain;
+        localization
+    }
+
+    /// The locales that messages are looked up in, in order.
+    ///
+    /// The chain starts with the [`Locale`](crate::locale::Locale), followed by its fallbacks
+    /// and the [`LocaleDefaultFallback`](crate::locale::LocaleDefaultFallback).
+    pub fn resolution_chain(&self) -> &[LanguageIdentifier] {
+        self.folder.resolution_chain()
+    }
+
+    /// The resolution chain that is used once its localization files are loaded, if any.
+    ///
+    /// See [`LocaleSwitchTimeout`] for more information.
+    pub fn pending_resolution_chain(&self) -> Option<&[LanguageIdentifier]> {
+        self.folder.pending_resolution_chain()
+    }
+
+    /// The folders layered on top of the localization folder, in order of increasing precedence.
+    ///
+    /// See [`FolderLocalization`] for more information on layers.
+    pub fn layers(&self) -> &[String] {
+        self.folder.layers()
+    }
+
+    /// Add a folder as the new top layer, overriding the messages of all previous layers.
+    ///
+    /// This can be used to let DLCs or mods override the strings of the game:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_mod_localization::prelude::*;
+    /// #
+    /// #[derive(LocalizationFolder)]
+    /// #[folder_path = "strings/menu"]
+    /// struct MenuLocalizationFolder;
+    ///
+    /// fn load_mod(mut localization: ResMut<Localization<MenuLocalizationFolder>>) {
+    ///     // Messages in `/assets/mods/example/strings/menu` override the base game
+    ///     localization.add_layer("mods/example/strings/menu");
+    /// }
+    /// ```
+    ///
+    /// Returns `false` if the folder is already part of the localization.
+    pub fn add_layer(&mut self, layer_path: impl Into<String>) -> bool {
+        self.folder.add_layer(layer_path)
+    }
+
+    /// Remove a previously added layer.
+    ///
+    /// Returns `false` if the folder was not a layer of the localization.
+    pub fn remove_layer(&mut self, layer_path: &str) -> bool {
+        self.folder.remove_layer(layer_path)
+    }
+
+    /// Get the source that the given message is currently taken from.
+    ///
+    /// This is either [`LocalizationFolder::FOLDER_PATH`], one of the added layers
+    /// or a source inserted with [`Localization::insert_source`].
+    pub fn message_layer(&self, message_id: &str) -> Option<MessageLayer<'_>> {
+        self.folder.message_layer(message_id)
+    }
+
+    /// Insert a localization source for the given locale, bypassing the asset server.
+    ///
+    /// Inserted sources take precedence over the localization files.
+    /// This is useful for strings generated at runtime, for example in tests:
+    ///
+    /// ```
+    /// # use bevy_mod_localization::prelude::*;
+    /// #
+    /// # #[derive(LocalizationFolder)]
+    /// # #[folder_path = "strings/example"]
+    /// # struct ExampleLocalizationFolder;
+    /// #
+    /// let mut localization = Localization::<ExampleLocalizationFolder>::new(vec!["en-US".parse().unwrap()]);
+    /// localization.insert_source("en-US", "hello = Hello World!").unwrap();
+    ///
+    /// assert_eq!(localization.try_get_message("hello"), Ok("Hello World!".to_string()));
+    /// ```
+    ///
+    /// Returns an error if the locale is invalid or the source contains syntax errors.
+    pub fn insert_source<L: TryIntoLanguageIdentifier>(
+        &mut self,
+        locale: L,
+        ftl_string: impl Into<String>,
+    ) -> Result<(), LocalizationError> {
+        self.folder.insert_source(locale, ftl_string)
+    }
+
+    /// Remove all sources that have been inserted for the given locale.
+    ///
+    /// Returns `false` if no sources had been inserted for the locale.
+    pub fn remove_sources<L: TryIntoLanguageIdentifier>(&mut self, locale: L) -> bool {
+        self.folder.remove_sources(locale)
+    }
+
+    /// Find all messages that contain characters not covered by the given font.
+    ///
+    /// This can be used in a test to make sure that the font supports all loaded locales.
+    /// See the [`glyphs`](crate::glyphs) module for more information.
+    pub fn find_missing_glyphs(&self, font: &Font) -> Vec<MissingGlyphs> {
+        self.folder.find_missing_glyphs(font)
+    }
+
+    pub fn try_get_message(&self, message_id: &str) -> Result<String, LocalizationError> {
+        self.folder.try_get_message(message_id)
+    }
+
+    pub fn try_format_message(
+        &self,
+        message_id: &str,
+        args: FluentArgs,
+    ) -> Result<String, LocalizationError> {
+        self.folder.try_format_message(message_id, args)
     }
 
-    fn try_format_pattern(
+    /// Format a message into the given buffer, replacing its contents.
+    ///
+    /// Unlike [`Localization::try_format_message`], this doesn't allocate a new string,
+    /// so the buffer can be reused every frame:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_mod_localization::prelude::*;
+    /// # use fluent::FluentArgs;
+    /// #
+    /// # #[derive(LocalizationFolder)]
+    /// # #[folder_path = "strings/hud"]
+    /// # struct HudLocalizationFolder;
+    /// #
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// fn update_score_text(
+    ///     localization: Res<Localization<HudLocalizationFolder>>,
+    ///     mut query: Query<(&mut Text, &Score)>,
+    /// ) {
+    ///     for (mut text, score) in &mut query {
+    ///         let mut args = FluentArgs::new();
+    ///         args.set("score", score.0);
+    ///
+    ///         let section = &mut text.sections[0];
+    ///         let _ = localization.format_into(&mut section.value, "score", Some(&args));
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The format cache is not used.
+    /// If the message can't be formatted, the buffer is left empty.
+    pub fn format_into(
         &self,
+        buf: &mut String,
         message_id: &str,
         args: Option<&FluentArgs>,
-    ) -> Result<String, LocalizationError> {
-        for lang_id in &self.resolution_chain {
-            if let Some(bundle) = self.bundle_map.get(lang_id) {
-                if let Some(msg) = bundle.get_message(message_id) {
-                    let mut errors = vec![];
-
-                    if let Some(pattern) = msg.value() {
-                        let formatted_message = bundle.format_pattern(pattern, args, &mut errors);
-
-                        if errors.is_empty() {
-                            return Ok(formatted_message.to_string());
-                        }
-                    }
-                }
-            }
-        }
+    ) -> Result<(), LocalizationError> {
+        self.folder.format_into(buf, message_id, args)
+    }
+
+    /// Write a message to the given writer, without allocating an intermediate string.
+    ///
+    /// The format cache is not used.
+    /// Since the output can't be taken back, a message that fails to format is not looked up
+    /// in the next locale of the resolution chain, and the output may be incomplete.
+    pub fn write_message(
+        &self,
+        w: &mut impl fmt::Write,
+        message_id: &str,
+        args: Option<&FluentArgs>,
+    ) -> Result<(), LocalizationError> {
+        self.folder.write_message(w, message_id, args)
+    }
 
-        Err(LocalizationError)
+    /// Cache the formatted messages, so that messages requested every frame are only
+    /// formatted once.
+    ///
+    /// This is useful if the same messages are formatted every frame, e.g. for a HUD:
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_mod_localization::prelude::*;
+    /// # use fluent::FluentArgs;
+    /// #
+    /// # #[derive(LocalizationFolder)]
+    /// # #[folder_path = "strings/hud"]
+    /// # struct HudLocalizationFolder;
+    /// #
+    /// let mut localization = Localization::<HudLocalizationFolder>::new(vec!["en-US".parse().unwrap()]);
+    /// localization.insert_source("en-US", "score = Score: { $score }").unwrap();
+    /// localization.enable_format_cache(256);
+    ///
+    /// for _ in 0..3 {
+    ///     let mut args = FluentArgs::new();
+    ///     args.set("score", 42);
+    ///     assert_eq!(localization.try_format_message("score", args), Ok("Score: 42".to_string()));
+    /// }
+    ///
+    /// let stats = localization.format_cache_stats().unwrap();
+    /// assert_eq!((stats.hits, stats.misses), (2, 1));
+    /// ```
+    ///
+    /// Only messages resolved through the active resolution chain are cached.
+    /// A cached message is formatted again once it changes, e.g. because the locale changed
+    /// or its localization file has been reloaded.
+    /// When the cache holds `capacity` messages, the least recently used one is dropped.
+    ///
+    /// The hits and misses can be monitored with the
+    /// [`LocalizationDiagnosticsPlugin`](crate::diagnostics::LocalizationDiagnosticsPlugin).
+    pub fn enable_format_cache(&mut self, capacity: usize) {
+        self.folder.enable_format_cache(capacity);
     }
 
-    pub fn try_get_message(&self, message_id: &str) -> Result<String, LocalizationError> {
-        self.try_format_pattern(message_id, None)
+    /// Stop caching formatted messages, dropping the cache.
+    pub fn disable_format_cache(&mut self) {
+        self.folder.disable_format_cache();
     }
 
-    pub fn try_format_message(
+    /// The statistics of the format cache, if it is enabled.
+    pub fn format_cache_stats(&self) -> Option<FormatCacheStats> {
+        self.folder.format_cache_stats()
+    }
+
+    /// Get a message, resolved through the given resolution chain instead of the active one.
+    ///
+    /// Only locales that are loaded are considered, i.e. the locales of the active resolution
+    /// chain and of the [`LocaleOverride`](crate::locale::LocaleOverride) components.
+    pub fn try_get_message_in(
         &self,
+        resolution_chain: &[LanguageIdentifier],
+        message_id: &str,
+    ) -> Result<String, LocalizationError> {
+        self.folder.try_get_message_in(resolution_chain, message_id)
+    }
+
+    /// Format a message, resolved through the given resolution chain instead of the active one.
+    ///
+    /// See [`Localization::try_get_message_in`] for the locales that are considered.
+    pub fn try_format_message_in(
+        &self,
+        resolution_chain: &[LanguageIdentifier],
         message_id: &str,
         args: FluentArgs,
     ) -> Result<String, LocalizationError> {
-        self.try_format_pattern(message_id, Some(&args))
-            // The inserted values are wrapped in U+2058 (First Strong Isolate) and U+2069 (Pop Directional Isolate)
-            // The font can't handle them, so we replace them for now
-            // TODO: Don't do this
-            .map(|msg| msg.replace(['\u{2068}', '\u{2069}'], ""))
+        self.folder
+            .try_format_message_in(resolution_chain, message_id, args)
     }
 }
 
 impl<T: LocalizationFolder> Default for Localization<T> {
     fn default() -> Self {
+        let mut folder = FolderLocalization::new(T::FOLDER_PATH);
+        folder.embedded_sources = T::EMBEDDED_SOURCES;
+
         Self {
             phantom: PhantomData,
-            resolution_chain: Vec::new(),
-            handle_map: HashMap::default(),
-            bundle_map: HashMap::default(),
+            folder,
         }
     }
 }
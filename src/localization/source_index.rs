@@ -0,0 +1,154 @@
+use std::any::TypeId;
+
+use bevy::{
+    asset::HandleId,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use super::{FolderLocalization, LocalizationFolder};
+use crate::LocalizationSource;
+
+/// Identifies the localization folder that a localization file belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum FolderKey {
+    /// The folder of a [`Localization`](super::Localization) resource.
+    Typed(TypeId),
+    /// A folder of the [`Localizations`](super::Localizations) resource, by its path.
+    Runtime(String),
+}
+
+impl FolderKey {
+    pub(crate) fn typed<T: LocalizationFolder>() -> Self {
+        Self::Typed(TypeId::of::<T>())
+    }
+}
+
+/// The folders that each localization file belongs to.
+///
+/// The asset events are routed to their folders once per update,
+/// so the folders don't have to check every event themselves.
+#[derive(Default, Resource)]
+pub(crate) struct LocalizationSourceIndex {
+    folders: HashMap<HandleId, Vec<FolderKey>>,
+    /// The localization files of each folder, to update the index when they change.
+    sources: HashMap<FolderKey, Vec<HandleId>>,
+    /// The asset events of the current update, by the folder they belong to.
+    events: HashMap<FolderKey, Vec<AssetEvent<LocalizationSource>>>,
+    /// The folders that wait for the files of a new resolution chain.
+    switching_folders: HashSet<FolderKey>,
+}
+
+impl LocalizationSourceIndex {
+    /// Index the localization files of the folder, if they have changed.
+    ///
+    /// This also keeps track of the folders that are switching to a new resolution chain.
+    pub(crate) fn sync(&mut self, key: FolderKey, folder: &mut FolderLocalization) {
+        if folder.sources_changed {
+            folder.sources_changed = false;
+            self.remove_folder(&key);
+
+            let ids: Vec<HandleId> = folder.source_ids().collect();
+            for id in &ids {
+                self.folders.entry(*id).or_default().push(key.clone());
+            }
+            self.sources.insert(key.clone(), ids);
+        }
+
+        if folder.pending_chain.is_some() {
+            self.switching_folders.insert(key);
+        } else {
+            self.switching_folders.remove(&key);
+        }
+    }
+
+    /// Remove the localization files of the folder from the index.
+    pub(crate) fn remove_folder(&mut self, key: &FolderKey) {
+        self.switching_folders.remove(key);
+
+        for id in self.sources.remove(key).into_iter().flatten() {
+            if let Some(keys) = self.folders.get_mut(&id) {
+                keys.retain(|folder_key| folder_key != key);
+
+                if keys.is_empty() {
+                    self.folders.remove(&id);
+                }
+            }
+        }
+    }
+
+    /// Remove the runtime folders that don't pass the predicate, i.e. that have been removed.
+    pub(crate) fn retain_runtime_folders(&mut self, mut predicate: impl FnMut(&str) -> bool) {
+        let removed: Vec<FolderKey> = self
+            .sources
+            .keys()
+            .filter(|key| matches!(key, FolderKey::Runtime(path) if !predicate(path)))
+            .cloned()
+            .collect();
+
+        for key in &removed {
+            self.remove_folder(key);
+        }
+    }
+
+    /// Determine if any folder still waits for the files of a new resolution chain.
+    pub(crate) fn is_switching_locale(&self) -> bool {
+        !self.switching_folders.is_empty()
+    }
+
+    /// The asset events of the current update for the given folder.
+    pub(crate) fn events(&self, key: &FolderKey) -> &[AssetEvent<LocalizationSource>] {
+        self.events.get(key).map_or(&[], Vec::as_slice)
+    }
+
+    /// The asset events of the current update for the runtime folders, by folder path.
+    pub(crate) fn runtime_events(
+        &self,
+    ) -> impl Iterator<Item = (&str, &[AssetEvent<LocalizationSource>])> {
+        self.events.iter().filter_map(|(key, events)| match key {
+            FolderKey::Runtime(path) => Some((path.as_str(), events.as_slice())),
+            FolderKey::Typed(_) => None,
+        })
+    }
+}
+
+/// Route the asset events of the localization files to the folders they belong to.
+pub(crate) fn route_localization_asset_events(
+    mut index: ResMut<LocalizationSourceIndex>,
+    mut ev_asset: EventReader<AssetEvent<LocalizationSource>>,
+) {
+    let LocalizationSourceIndex {
+        folders, events, ..
+    } = &mut *index;
+    events.clear();
+
+    for ev in ev_asset.iter() {
+        for key in folders.get(&event_handle(ev).id()).into_iter().flatten() {
+            events.entry(key.clone()).or_default().push(weak_event(ev));
+        }
+    }
+}
+
+/// Get the handle of the asset that the event refers to.
+fn event_handle(ev: &AssetEvent<LocalizationSource>) -> &Handle<LocalizationSource> {
+    match ev {
+        AssetEvent::Created { handle }
+        | AssetEvent::Modified { handle }
+        | AssetEvent::Removed { handle } => handle,
+    }
+}
+
+/// Copy the event, which doesn't implement `Clone`.
+fn weak_event(ev: &AssetEvent<LocalizationSource>) -> AssetEvent<LocalizationSource> {
+    match ev {
+        AssetEvent::Created { handle } => AssetEvent::Created {
+            handle: handle.clone_weak(),
+        },
+        AssetEvent::Modified { handle } => AssetEvent::Modified {
+            handle: handle.clone_weak(),
+        },
+        AssetEvent::Removed { handle } => AssetEvent::Removed {
+            handle: handle.clone_weak(),
+        },
+    }
+}
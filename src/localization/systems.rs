@@ -1,55 +1,115 @@
-use bevy::prelude::*;
-use fluent::FluentResource;
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
 use unic_langid::LanguageIdentifier;
 
 use super::{
-    components::LocalizedText,
-    utils::{get_ftl_path, get_resolution_chain},
-    Localization, LocalizationFolder,
+    components::{DynamicLocalizedText, LocalizedText},
+    font::LocalizedFont,
+    source_index::{FolderKey, LocalizationSourceIndex},
+    utils::get_resolution_chain,
+    FolderLocalization, LocaleSwitchTimeout, Localization, LocalizationFolder,
+    LocalizationLoadPolicy, Localizations,
 };
 use crate::{
-    fluent::FluentBundle,
-    locale::{Locale, LocaleDefaultFallback, LocaleFallbackMap},
+    locale::{
+        locale_override::{LocaleOverrides, OverriddenLocales},
+        Locale, LocaleDefaultFallback, LocaleFallbackMap,
+    },
     LocalizationSource,
 };
 
+/// The settings determining which locales are loaded besides the resolution chain.
+#[derive(SystemParam)]
+pub(crate) struct AdditionalLocales<'w> {
+    overridden_locales: Res<'w, OverriddenLocales>,
+    load_policy: Res<'w, LocalizationLoadPolicy>,
+}
+
+impl<'w> AdditionalLocales<'w> {
+    fn is_changed(&self) -> bool {
+        self.overridden_locales.is_changed() || self.load_policy.is_changed()
+    }
+
+    /// Determine if the folder already uses the current settings.
+    fn is_applied(&self, folder: &FolderLocalization) -> bool {
+        folder.override_locales == self.overridden_locales.0
+            && folder.load_policy == *self.load_policy
+    }
+
+    /// Use the current settings for the folder.
+    fn apply(&self, folder: &mut FolderLocalization) {
+        folder.set_override_locales(self.overridden_locales.0.clone());
+        folder.load_policy = self.load_policy.clone();
+    }
+}
+
+/// The state needed to decide when to switch to a pending resolution chain.
+///
+/// Without the [`Time`] resource, e.g. if the `TimePlugin` isn't added,
+/// the localizations wait for the files of the new chain without a timeout.
+#[derive(SystemParam)]
+pub(crate) struct PendingChains<'w> {
+    time: Option<Res<'w, Time>>,
+    timeout: Res<'w, LocaleSwitchTimeout>,
+}
+
+impl<'w> PendingChains<'w> {
+    /// Switch the folder to its pending resolution chain, if it is ready.
+    ///
+    /// Returns `true` if the resolution chain has been switched.
+    fn update(&self, folder: &mut FolderLocalization, asset_server: &AssetServer) -> bool {
+        let now = self.time.as_ref().map(|time| time.raw_elapsed());
+        folder.update_pending_chain(asset_server, now, self.timeout.get())
+    }
+}
+
 /// Load the corresponding localization file when the locale has been changed.
+///
+/// The files of the locales used by [`LocaleOverride`](crate::locale::LocaleOverride)
+/// components are loaded as well.
+#[allow(clippy::too_many_arguments)]
 pub fn update_localization_on_locale_change<T: LocalizationFolder>(
     mut localization: ResMut<Localization<T>>,
-    asset_server: ResMut<AssetServer>,
+    mut source_index: ResMut<LocalizationSourceIndex>,
+    asset_server: Res<AssetServer>,
+    mut assets: ResMut<Assets<LocalizationSource>>,
     locale: Res<Locale>,
     fallback_map: Res<LocaleFallbackMap>,
     default_fallback: Res<LocaleDefaultFallback>,
+    additional_locales: AdditionalLocales,
 ) {
-    if locale.is_changed() || fallback_map.is_changed() || default_fallback.is_changed() {
-        // Compute the new resolution chain
-        let resolution_chain = get_resolution_chain(&locale, &fallback_map, &default_fallback);
+    let chain_changed =
+        locale.is_changed() || fallback_map.is_changed() || default_fallback.is_changed();
+    let sources_changed = additional_locales.is_changed() || localization.folder.layers_changed;
 
-        let handle_keys: Vec<LanguageIdentifier> =
-            localization.handle_map.keys().cloned().collect();
+    // The text only needs to be updated once the new resolution chain is active
+    let folder = &mut localization.bypass_change_detection().folder;
 
-        // Load handles that are now needed
-        for lang_id in resolution_chain
-            .iter()
-            .filter(|lang_id| !handle_keys.contains(lang_id))
-        {
-            let ftl_path = get_ftl_path::<T>(lang_id);
-            let handle = asset_server.load(ftl_path);
-            localization.handle_map.insert(lang_id.clone(), handle);
+    if chain_changed || sources_changed {
+        let previous_chain = folder.resolution_chain.clone();
+
+        if additional_locales.is_changed() {
+            additional_locales.apply(folder);
         }
 
-        // Remove handles that are no longer needed
-        for lang_id in handle_keys
-            .iter()
-            .filter(|lang_id| !resolution_chain.contains(lang_id))
-        {
-            localization.handle_map.remove(lang_id);
-            localization.bundle_map.remove(lang_id);
+        if chain_changed {
+            // Compute the new resolution chain
+            let resolution_chain =
+                get_resolution_chain(&locale.0, &fallback_map, &default_fallback);
+            folder.set_resolution_chain(resolution_chain, &asset_server, &mut assets);
+        } else {
+            folder.load_sources(&asset_server, &mut assets);
         }
 
-        // Update resolution chain
-        localization.resolution_chain = resolution_chain;
+        if sources_changed || folder.resolution_chain != previous_chain {
+            localization.set_changed();
+        }
     }
+
+    // This also indexes the files loaded when the localization has been added
+    source_index.sync(
+        FolderKey::typed::<T>(),
+        &mut localization.bypass_change_detection().folder,
+    );
 }
 
 /// Update the localization resource when a localization asset has been changed.
@@ -58,73 +118,316 @@ pub fn update_localization_on_locale_change<T: LocalizationFolder>(
 /// - The localization file has been loaded for the first time.
 /// - The localization file has been edited and hot-reloading is enabled.
 /// - The locale has been changed, so a new localization file has been loaded.
+///
+/// The localization switches to a new resolution chain once its files are loaded.
 pub fn update_localization_on_asset_change<T: LocalizationFolder>(
     mut localization: ResMut<Localization<T>>,
-    mut ev_asset: EventReader<AssetEvent<LocalizationSource>>,
-    assets: ResMut<Assets<LocalizationSource>>,
+    mut source_index: ResMut<LocalizationSourceIndex>,
+    assets: Res<Assets<LocalizationSource>>,
+    asset_server: Res<AssetServer>,
+    pending_chains: PendingChains,
 ) {
-    for ev in ev_asset.iter() {
-        match ev {
-            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
-                let lang_id = localization.handle_map.iter().find_map(|(key, value)| {
-                    if *value == *handle {
-                        Some(key.clone())
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(lang_id) = lang_id {
-                    let source = assets
-                        .get(handle)
-                        .expect("Localization source expected to be loaded but it wasn't!");
-
-                    // TODO: Make this more efficient, the parsing could take some time
-                    let resource = FluentResource::try_new(source.ftl_string.clone())
-                        .expect("Failed to parse an FTL string.");
-
-                    let mut bundle = FluentBundle::new_concurrent(vec![lang_id.clone()]);
-                    bundle
-                        .add_resource(resource)
-                        .expect("Failed to add resource to bundle");
-
-                    localization.bundle_map.insert(lang_id.clone(), bundle);
-                }
+    let mut changed = false;
+    let key = FolderKey::typed::<T>();
+    let folder = &mut localization.bypass_change_detection().folder;
+
+    for ev in source_index.events(&key) {
+        // Files of locales that are not looked up yet don't affect the text
+        changed |= folder.handle_asset_event(ev, &assets);
+    }
+
+    if folder.pending_chain.is_some() {
+        changed |= pending_chains.update(folder, &asset_server);
+    }
+
+    // The files of the previous resolution chain are dropped after a switch
+    source_index.sync(key, folder);
+
+    if changed {
+        localization.set_changed();
+    }
+}
+
+/// Update the text of the [`LocalizedText`] components.
+///
+/// Only the text of messages that have changed is updated,
+/// as well as the text of components that have been added or changed.
+pub fn update_localized_text<T: LocalizationFolder>(
+    mut query: Query<(Entity, &mut Text, Ref<LocalizedText<T>>)>,
+    changed_texts: Query<Entity, Changed<LocalizedText<T>>>,
+    localization: Res<Localization<T>>,
+    localized_font: Option<Res<LocalizedFont>>,
+    mut overrides: LocaleOverrides,
+    mut last_generation: Local<u64>,
+) {
+    let font_changed = localized_font
+        .as_ref()
+        .is_some_and(|font| font.is_changed());
+    let overrides_changed = overrides.is_changed();
+    let localized_font = localized_font.as_deref();
+
+    let generations = &localization.folder.generations;
+    let update_all = localization.is_added()
+        || font_changed
+        || overrides_changed
+        || generations.is_all_changed_since(*last_generation);
+
+    if update_all || localization.is_changed() {
+        for (entity, mut text, localized_text) in query.iter_mut() {
+            let message_id = localized_text.message_id();
+
+            if update_all
+                || localized_text.is_changed()
+                || generations.is_changed_since(message_id, *last_generation)
+            {
+                let resolution_chain = overrides.resolution_chain(entity);
+                localize_text(
+                    &mut text,
+                    Some(&localization.folder),
+                    message_id,
+                    resolution_chain,
+                    localized_font,
+                );
             }
-            AssetEvent::Removed { handle } => {
-                let lang_id = localization.handle_map.iter().find_map(|(key, value)| {
-                    if *value == *handle {
-                        Some(key.clone())
-                    } else {
-                        None
-                    }
-                });
-
-                if let Some(lang_id) = lang_id {
-                    localization.bundle_map.remove(&lang_id);
-                }
+        }
+    } else {
+        for entity in &changed_texts {
+            if let Ok((entity, mut text, localized_text)) = query.get_mut(entity) {
+                let resolution_chain = overrides.resolution_chain(entity);
+                localize_text(
+                    &mut text,
+                    Some(&localization.folder),
+                    localized_text.message_id(),
+                    resolution_chain,
+                    localized_font,
+                );
             }
         }
     }
+
+    *last_generation = generations.current();
 }
 
-pub fn update_localized_text<T: LocalizationFolder>(
-    mut query: Query<(&mut Text, &LocalizedText<T>)>,
-    localization: Res<Localization<T>>,
+/// Load the localization files of the runtime folders.
+///
+/// This happens when the locale has been changed or when new folders or layers have been added.
+#[allow(clippy::too_many_arguments)]
+pub fn update_localizations_on_locale_change(
+    mut localizations: ResMut<Localizations>,
+    mut source_index: ResMut<LocalizationSourceIndex>,
+    asset_server: Res<AssetServer>,
+    mut assets: ResMut<Assets<LocalizationSource>>,
+    locale: Res<Locale>,
+    fallback_map: Res<LocaleFallbackMap>,
+    default_fallback: Res<LocaleDefaultFallback>,
+    additional_locales: AdditionalLocales,
 ) {
-    if localization.is_changed() || localization.is_added() {
-        for (mut text, localized_text) in query.iter_mut() {
-            if let Ok(msg) = localization.try_get_message(localized_text.message_id()) {
-                // Update the text with the localization
-                if let Some(mut section) = text.sections.first_mut() {
-                    // The text already has a section; update it
-                    section.value = msg;
-                } else {
-                    // The text doesn't have sections yet; add a new one
-                    let section = TextSection::new(msg, TextStyle::default());
-                    text.sections.push(section);
-                }
+    if localizations.is_changed() {
+        // Some folders might have been removed
+        source_index
+            .retain_runtime_folders(|folder_path| localizations.contains_folder(folder_path));
+    }
+
+    if locale.is_changed()
+        || fallback_map.is_changed()
+        || default_fallback.is_changed()
+        || additional_locales.is_changed()
+        || localizations.is_changed()
+    {
+        let resolution_chain = get_resolution_chain(&locale.0, &fallback_map, &default_fallback);
+
+        // Only touch the folders that are out of date to not trigger change detection every frame
+        let outdated_folders: Vec<String> = localizations
+            .folders
+            .iter()
+            .filter(|(_, folder)| {
+                folder.target_resolution_chain() != resolution_chain
+                    || !additional_locales.is_applied(folder)
+                    || folder.layers_changed
+            })
+            .map(|(folder_path, _)| folder_path.clone())
+            .collect();
+
+        let mut changed = false;
+
+        for folder_path in outdated_folders {
+            // The text only needs to be updated once the new resolution chain is active
+            let Some(folder) = localizations
+                .bypass_change_detection()
+                .folders
+                .get_mut(&folder_path)
+            else {
+                continue;
+            };
+
+            let previous_chain = folder.resolution_chain.clone();
+            let sources_changed = !additional_locales.is_applied(folder) || folder.layers_changed;
+
+            additional_locales.apply(folder);
+            folder.set_resolution_chain(resolution_chain.clone(), &asset_server, &mut assets);
+
+            changed |= sources_changed || folder.resolution_chain != previous_chain;
+            source_index.sync(FolderKey::Runtime(folder_path), folder);
+        }
+
+        if changed {
+            localizations.set_changed();
+        }
+    }
+}
+
+/// Update the runtime folders when a localization asset has been changed.
+pub fn update_localizations_on_asset_change(
+    mut localizations: ResMut<Localizations>,
+    mut source_index: ResMut<LocalizationSourceIndex>,
+    assets: Res<Assets<LocalizationSource>>,
+    asset_server: Res<AssetServer>,
+    pending_chains: PendingChains,
+) {
+    let mut changed = false;
+
+    // Don't trigger change detection for unrelated assets
+    let folders = &mut localizations.bypass_change_detection().folders;
+
+    for (folder_path, events) in source_index.runtime_events() {
+        if let Some(folder) = folders.get_mut(folder_path) {
+            for ev in events {
+                // Files of locales that are not looked up yet don't affect the text
+                changed |= folder.handle_asset_event(ev, &assets);
+            }
+        }
+    }
+
+    for (folder_path, folder) in folders.iter_mut() {
+        if folder.pending_chain.is_some() {
+            changed |= pending_chains.update(folder, &asset_server);
+
+            // The files of the previous resolution chain are dropped after a switch
+            source_index.sync(FolderKey::Runtime(folder_path.clone()), folder);
+        }
+    }
+
+    if changed {
+        localizations.set_changed();
+    }
+}
+
+/// Update the text of the [`DynamicLocalizedText`] components.
+///
+/// Only the text of messages that have changed is updated,
+/// as well as the text of components that have been added or changed.
+pub fn update_dynamic_localized_text(
+    mut query: Query<(Entity, &mut Text, Ref<DynamicLocalizedText>)>,
+    changed_texts: Query<Entity, Changed<DynamicLocalizedText>>,
+    localizations: Res<Localizations>,
+    localized_font: Option<Res<LocalizedFont>>,
+    mut overrides: LocaleOverrides,
+    mut last_generations: Local<HashMap<String, u64>>,
+) {
+    let font_changed = localized_font
+        .as_ref()
+        .is_some_and(|font| font.is_changed());
+    let overrides_changed = overrides.is_changed();
+    let localized_font = localized_font.as_deref();
+
+    let update_all = font_changed || overrides_changed;
+
+    if update_all || localizations.is_changed() {
+        for (entity, mut text, localized_text) in query.iter_mut() {
+            let folder = localizations.get(localized_text.folder_path());
+            let message_id = localized_text.message_id();
+
+            // Folders that haven't been seen before are new
+            let message_changed = folder.is_some_and(|folder| {
+                last_generations
+                    .get(localized_text.folder_path())
+                    .map_or(true, |generation| {
+                        folder.generations.is_changed_since(message_id, *generation)
+                    })
+            });
+
+            if update_all || localized_text.is_changed() || message_changed {
+                let resolution_chain = overrides.resolution_chain(entity);
+                localize_text(
+                    &mut text,
+                    folder,
+                    message_id,
+                    resolution_chain,
+                    localized_font,
+                );
+            }
+        }
+
+        *last_generations = localizations
+            .folders
+            .iter()
+            .map(|(folder_path, folder)| (folder_path.clone(), folder.generations.current()))
+            .collect();
+    } else {
+        for entity in &changed_texts {
+            if let Ok((entity, mut text, localized_text)) = query.get_mut(entity) {
+                let resolution_chain = overrides.resolution_chain(entity);
+                localize_text(
+                    &mut text,
+                    localizations.get(localized_text.folder_path()),
+                    localized_text.message_id(),
+                    resolution_chain,
+                    localized_font,
+                );
             }
         }
     }
 }
+
+/// Localize the text with the given message.
+///
+/// If the entity is part of a [`LocaleOverride`](crate::locale::LocaleOverride),
+/// the message is resolved through the resolution chain of the override.
+fn localize_text(
+    text: &mut Text,
+    folder: Option<&FolderLocalization>,
+    message_id: &str,
+    override_chain: Option<&[LanguageIdentifier]>,
+    localized_font: Option<&LocalizedFont>,
+) {
+    let (msg, font) = match override_chain {
+        Some(resolution_chain) => (
+            folder.and_then(|folder| folder.try_get_message_in(resolution_chain, message_id).ok()),
+            localized_font.and_then(|font| font.font_for(resolution_chain)),
+        ),
+        None => (
+            folder.and_then(|folder| folder.try_get_message(message_id).ok()),
+            // Keep the font of the chain the message is taken from while switching the locale
+            localized_font.and_then(|font| {
+                folder
+                    .and_then(|folder| font.font_for(&folder.resolution_chain))
+                    .or_else(|| font.font())
+            }),
+        ),
+    };
+
+    set_text_message(text, msg, font);
+}
+
+/// Update the text with the localization.
+///
+/// If a localized font is given, it is applied to the localized section as well.
+fn set_text_message(text: &mut Text, msg: Option<String>, font: Option<&Handle<Font>>) {
+    if let Some(section) = text.sections.first_mut() {
+        // The text already has a section; update it
+        if let Some(msg) = msg {
+            section.value = msg;
+        }
+
+        if let Some(font) = font {
+            section.style.font = font.clone();
+        }
+    } else if let Some(msg) = msg {
+        // The text doesn't have sections yet; add a new one
+        let style = TextStyle {
+            font: font.cloned().unwrap_or_default(),
+            ..default()
+        };
+        text.sections.push(TextSection::new(msg, style));
+    }
+}
@@ -1,35 +1,43 @@
 use std::path::{Path, PathBuf};
 
+use bevy::prelude::error;
 use unic_langid::LanguageIdentifier;
 
-use crate::locale::{Locale, LocaleDefaultFallback, LocaleFallbackMap};
-
-use super::LocalizationFolder;
+use crate::locale::{LocaleDefaultFallback, LocaleFallbackMap};
 
 /// Get the path of the FTL file for the given language ID in the localization folder.
-pub fn get_ftl_path<T: LocalizationFolder>(lang_id: &LanguageIdentifier) -> PathBuf {
-    Path::new(&T::FOLDER_PATH).join(format!("{}.ftl", lang_id))
+pub fn get_ftl_path(folder_path: &str, lang_id: &LanguageIdentifier) -> PathBuf {
+    Path::new(folder_path).join(format!("{}.ftl", lang_id))
+}
+
+/// Remove the leading slash of a folder path, so it is relative to the asset folder.
+///
+/// Folder paths may be written like `/strings/example`, which would otherwise replace the
+/// asset folder when joined with it.
+pub(crate) fn asset_folder_path(folder_path: &str) -> &str {
+    folder_path.trim_start_matches('/')
 }
 
+/// Get the locales to try, in order: the locale, its fallbacks and the default fallback.
+///
+/// A cycle in the transitive fallbacks is logged as an error, the resolution chain is still
+/// usable in that case.
 pub fn get_resolution_chain(
-    locale: &Locale,
+    locale: &LanguageIdentifier,
     fallback_map: &LocaleFallbackMap,
     default_fallback: &LocaleDefaultFallback,
 ) -> Vec<LanguageIdentifier> {
-    let locale = locale.0.clone();
-    let fallbacks = fallback_map.0.get(&locale);
-    let default_fallback = default_fallback.0.clone();
-
-    let mut resolution_chain = Vec::with_capacity(fallbacks.map_or(0, |f| f.len()) + 2);
-
-    resolution_chain.push(locale);
+    let (mut resolution_chain, cycle) = fallback_map.resolve(locale);
 
-    if let Some(fallbacks) = fallbacks {
-        resolution_chain.append(&mut fallbacks.clone());
+    if let Some(cycle) = cycle {
+        error!("Invalid fallbacks for locale {locale}: {cycle}");
     }
 
-    if let Some(default_fallback) = default_fallback {
-        resolution_chain.push(default_fallback);
+    if let Some(default_fallback) = &default_fallback.0 {
+        // The transitive fallbacks contain every locale only once
+        if !(fallback_map.transitive && resolution_chain.contains(default_fallback)) {
+            resolution_chain.push(default_fallback.clone());
+        }
     }
 
     resolution_chain
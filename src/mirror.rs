@@ -0,0 +1,102 @@
+//! Mirror UI layouts for right-to-left locales.
+
+use bevy::prelude::*;
+
+use crate::locale::LocaleDirection;
+
+/// Mirror the layout of the entity horizontally if the active locale is written from right to left.
+///
+/// The following properties are mirrored:
+///
+/// - The [`Style::flex_direction`], swapping [`FlexDirection::Row`] and [`FlexDirection::RowReverse`].
+/// - The [`Style::align_items`] of column layouts, swapping start and end.
+/// - The left and right of [`Style::position`], [`Style::margin`], [`Style::padding`]
+///   and [`Style::border`].
+/// - The [`Text::alignment`], swapping [`TextAlignment::Left`] and [`TextAlignment::Right`].
+///
+/// The entity should be laid out for left-to-right locales,
+/// it is mirrored whenever the [`LocaleDirection`] changes.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_mod_localization::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         NodeBundle {
+///             style: Style {
+///                 flex_direction: FlexDirection::Row,
+///                 ..default()
+///             },
+///             ..default()
+///         },
+///         MirrorForRtl::default(),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Default, Component)]
+pub struct MirrorForRtl {
+    mirrored: bool,
+}
+
+impl MirrorForRtl {
+    /// Determine if the entity is currently mirrored.
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+}
+
+/// Mirror the entities tagged with [`MirrorForRtl`] to match the [`LocaleDirection`].
+pub fn mirror_for_rtl(
+    direction: Res<LocaleDirection>,
+    mut query: Query<(&mut MirrorForRtl, Option<&mut Style>, Option<&mut Text>)>,
+) {
+    let should_mirror = direction.is_right_to_left();
+
+    for (mut mirror, style, text) in query.iter_mut() {
+        if mirror.mirrored == should_mirror {
+            continue;
+        }
+
+        if let Some(mut style) = style {
+            mirror_style(&mut style);
+        }
+
+        if let Some(mut text) = text {
+            text.alignment = match text.alignment {
+                TextAlignment::Left => TextAlignment::Right,
+                TextAlignment::Right => TextAlignment::Left,
+                TextAlignment::Center => TextAlignment::Center,
+            };
+        }
+
+        mirror.mirrored = should_mirror;
+    }
+}
+
+/// Mirror the style horizontally.
+fn mirror_style(style: &mut Style) {
+    match style.flex_direction {
+        FlexDirection::Row => style.flex_direction = FlexDirection::RowReverse,
+        FlexDirection::RowReverse => style.flex_direction = FlexDirection::Row,
+        // In column layouts, the cross axis is horizontal
+        FlexDirection::Column | FlexDirection::ColumnReverse => {
+            style.align_items = match style.align_items {
+                AlignItems::Start => AlignItems::End,
+                AlignItems::End => AlignItems::Start,
+                AlignItems::FlexStart => AlignItems::FlexEnd,
+                AlignItems::FlexEnd => AlignItems::FlexStart,
+                other => other,
+            };
+        }
+    }
+
+    for rect in [
+        &mut style.position,
+        &mut style.margin,
+        &mut style.padding,
+        &mut style.border,
+    ] {
+        std::mem::swap(&mut rect.left, &mut rect.right);
+    }
+}
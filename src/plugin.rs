@@ -2,7 +2,21 @@ use bevy::prelude::*;
 
 use crate::{
     loaders::ftl_loader::FtlLoader,
-    locale::{LocaleDefaultFallback, LocaleFallbackMap},
+    locale::{
+        direction::update_locale_direction,
+        locale_override::{update_locale_overrides, OverriddenLocales},
+        Locale, LocaleDefaultFallback, LocaleDirection, LocaleFallbackMap, LocaleOverride,
+    },
+    localization::{
+        font::update_localized_font,
+        source_index::{route_localization_asset_events, LocalizationSourceIndex},
+        systems::{
+            update_dynamic_localized_text, update_localizations_on_asset_change,
+            update_localizations_on_locale_change,
+        },
+        DynamicLocalizedText, LocaleSwitchTimeout, LocalizationLoadPolicy, Localizations,
+        LocalizedFont,
+    },
     LocalizationOutput, LocalizationSource,
 };
 
@@ -10,6 +24,8 @@ use crate::{
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum LocalizationSet {
     HandleChanges,
+    /// Measure the [`diagnostics`](crate::diagnostics), after the changes have been handled.
+    Diagnostics,
 }
 
 #[derive(Default)]
@@ -19,6 +35,36 @@ impl Plugin for LocalizationPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<LocaleFallbackMap>();
         app.init_resource::<LocaleDefaultFallback>();
+        app.init_resource::<LocaleDirection>();
+        app.init_resource::<OverriddenLocales>();
+        app.init_resource::<LocalizationLoadPolicy>();
+        app.init_resource::<LocaleSwitchTimeout>();
+        app.init_resource::<LocalizationSourceIndex>();
+
+        app.register_type::<Locale>()
+            .register_type::<LocaleFallbackMap>()
+            .register_type::<LocaleDefaultFallback>()
+            .register_type::<LocaleOverride>()
+            .register_type::<DynamicLocalizedText>();
+
+        // Restore the saved locale before any localization resolves its locales
+        #[cfg(feature = "ron")]
+        if app
+            .world
+            .contains_resource::<crate::locale::LocalePersistence>()
+        {
+            app.world.resource_scope(
+                |world, mut persistence: Mut<crate::locale::LocalePersistence>| {
+                    persistence.restore(world);
+                },
+            );
+
+            app.add_system(
+                crate::locale::persistence::save_locale_settings
+                    .run_if(resource_exists::<Locale>())
+                    .in_base_set(CoreSet::Last),
+            );
+        }
 
         // What does this do??
         app.init_non_send_resource::<LocalizationOutput>()
@@ -26,8 +72,62 @@ impl Plugin for LocalizationPlugin {
 
         app.init_asset_loader::<FtlLoader>();
 
+        #[cfg(feature = "ron")]
+        app.add_asset::<crate::locale::LocaleFallbackConfig>()
+            .init_asset_loader::<crate::loaders::fallback_config_loader::FallbackConfigLoader>();
+
         // Handle the localization changes after the asset events are generated
         // For some reason, `.after(AssetSet::AssetEvents)` doesn't work
         app.configure_set(LocalizationSet::HandleChanges.in_base_set(CoreSet::Last));
+        app.configure_set(
+            LocalizationSet::Diagnostics
+                .in_base_set(CoreSet::Last)
+                .after(LocalizationSet::HandleChanges),
+        );
+
+        // Determine the locales of the overrides before the fonts and localizations load them
+        app.add_system(
+            update_locale_overrides
+                .in_base_set(CoreSet::Last)
+                .before(LocalizationSet::HandleChanges),
+        );
+
+        // Determine the font before the localized text is updated
+        app.add_system(
+            update_localized_font
+                .run_if(resource_exists::<Locale>().and_then(resource_exists::<LocalizedFont>()))
+                .in_base_set(CoreSet::Last)
+                .after(update_locale_overrides)
+                .before(LocalizationSet::HandleChanges),
+        );
+
+        // Determine the direction after the localizations have switched their resolution chains
+        app.add_system(
+            update_locale_direction
+                .run_if(resource_exists::<Locale>())
+                .in_base_set(CoreSet::Last)
+                .after(LocalizationSet::HandleChanges),
+        );
+
+        #[cfg(feature = "bevy_ui")]
+        app.add_system(
+            crate::mirror::mirror_for_rtl
+                .after(update_locale_direction)
+                .in_base_set(CoreSet::Last),
+        );
+
+        // Localization folders that are added at runtime
+        // The asset events are routed to the folders after their files have been loaded
+        app.init_resource::<Localizations>().add_systems(
+            (
+                update_localizations_on_locale_change,
+                route_localization_asset_events,
+                update_localizations_on_asset_change,
+                update_dynamic_localized_text,
+            )
+                .chain()
+                .distributive_run_if(resource_exists::<Locale>())
+                .in_set(LocalizationSet::HandleChanges),
+        );
     }
 }
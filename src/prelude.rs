@@ -1,7 +1,16 @@
 pub use crate::{
-    locale::{Locale, LocaleDefaultFallback, LocaleFallbackMap},
-    localization::{AddLocalization, Localization, LocalizationFolder, LocalizedText},
+    locale::{Locale, LocaleDefaultFallback, LocaleDirection, LocaleFallbackMap, LocaleOverride},
+    localization::{
+        AddLocalization, DynamicLocalizedText, LocaleSwitchTimeout, Localization,
+        LocalizationFolder, LocalizationLoadPolicy, Localizations, LocalizedFont, LocalizedText,
+    },
     plugin::LocalizationPlugin,
 };
 
+#[cfg(feature = "ron")]
+pub use crate::locale::{LoadLocaleFallbacks, LocalePersistence};
+
+#[cfg(feature = "bevy_ui")]
+pub use crate::mirror::MirrorForRtl;
+
 pub use bevy_mod_localization_derive::*;
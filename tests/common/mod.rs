@@ -0,0 +1,14 @@
+use std::fs;
+
+use tempfile::TempDir;
+
+/// Write the files to a new temporary folder, which is deleted when it is dropped.
+pub fn fixture(files: &[(&str, &str)]) -> TempDir {
+    let folder = tempfile::tempdir().unwrap();
+
+    for (file_name, source) in files {
+        fs::write(folder.path().join(file_name), source).unwrap();
+    }
+
+    folder
+}
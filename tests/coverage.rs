@@ -0,0 +1,136 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use bevy_mod_localization::{
+    coverage::{
+        default_fallback_coverage, folder_coverage, write_report, CoverageCount, FolderCoverage,
+        LocaleCoverage, ReportFormat,
+    },
+    prelude::*,
+};
+use common::fixture;
+
+fn count(translated: usize, total: usize) -> CoverageCount {
+    CoverageCount { translated, total }
+}
+
+const REFERENCE: &str = "\
+hello = Hello!
+button = Start
+    .tooltip = Start the game
+    .label = Start
+icon =
+    .alt = An icon
+-brand = Bevy
+";
+
+#[test]
+fn messages_and_attributes_are_counted_separately() {
+    let folder = fixture(&[
+        ("en-US.ftl", REFERENCE),
+        // Terms and messages unknown to the reference don't count
+        (
+            "de.ftl",
+            "button = Start\n    .label = Start\nicon = Symbol\nextra = Extra\n-marke = Bevy\n",
+        ),
+    ]);
+
+    let coverage = folder_coverage(folder.path(), &"en-US".parse().unwrap()).unwrap();
+
+    assert_eq!(
+        coverage.locales,
+        [
+            LocaleCoverage {
+                locale: "de".parse().unwrap(),
+                messages: count(1, 2),
+                attributes: count(1, 3),
+            },
+            LocaleCoverage {
+                locale: "en-US".parse().unwrap(),
+                messages: count(2, 2),
+                attributes: count(3, 3),
+            },
+        ]
+    );
+    assert_eq!(coverage.locales[0].percentage(), 40.0);
+}
+
+#[test]
+fn the_default_fallback_is_the_reference() {
+    let folder = fixture(&[("en-US.ftl", REFERENCE), ("de.ftl", "hello = Hallo!")]);
+
+    let coverage =
+        default_fallback_coverage(folder.path(), &LocaleDefaultFallback::new(Some("de"))).unwrap();
+    let without_fallback =
+        default_fallback_coverage(folder.path(), &LocaleDefaultFallback::new(None::<&str>));
+
+    assert_eq!(coverage.reference_locale.to_string(), "de");
+    assert_eq!(coverage.locales[1].messages, count(1, 1));
+    assert!(without_fallback.is_err());
+}
+
+fn report(format: ReportFormat) -> String {
+    let coverage = FolderCoverage {
+        folder_path: "strings/\"menu\"\\\n\u{1}".to_string(),
+        reference_locale: "en-US".parse().unwrap(),
+        locales: vec![
+            LocaleCoverage {
+                locale: "de".parse().unwrap(),
+                messages: count(1, 3),
+                attributes: count(0, 0),
+            },
+            LocaleCoverage {
+                locale: "en-US".parse().unwrap(),
+                messages: count(3, 3),
+                attributes: count(0, 0),
+            },
+        ],
+    };
+
+    let mut report = String::new();
+    write_report(&mut report, &[coverage], format).unwrap();
+    report
+}
+
+#[test]
+fn json_reports_escape_strings() {
+    let report = report(ReportFormat::Json);
+
+    assert!(report.contains(r#""folder": "strings/\"menu\"\\\n\u0001""#));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&report).unwrap(),
+        serde_json::json!([{
+            "folder": "strings/\"menu\"\\\n\u{1}",
+            "reference": "en-US",
+            "locales": [
+                {
+                    "locale": "de",
+                    "messages": { "translated": 1, "total": 3 },
+                    "attributes": { "translated": 0, "total": 0 },
+                    "percentage": 33.3,
+                },
+                {
+                    "locale": "en-US",
+                    "messages": { "translated": 3, "total": 3 },
+                    "attributes": { "translated": 0, "total": 0 },
+                    "percentage": 100.0,
+                },
+            ],
+        }])
+    );
+}
+
+#[test]
+fn markdown_reports_have_a_table_per_folder() {
+    assert!(report(ReportFormat::Markdown).ends_with(
+        "\
+Reference locale: `en-US`
+
+| Locale | Messages | Attributes | Coverage |
+| --- | ---: | ---: | ---: |
+| de | 1/3 | 0/0 | 33.3% |
+| en-US | 3/3 | 0/0 | 100.0% |
+"
+    ));
+}
@@ -0,0 +1,121 @@
+use bevy::{
+    asset::AssetPlugin,
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics, DiagnosticsPlugin},
+    prelude::*,
+};
+use bevy_mod_localization::{diagnostics::LocalizationDiagnosticsPlugin, prelude::*};
+
+const FOLDER_PATH: &str = "strings/diagnostics";
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/diagnostics"]
+struct DiagnosticsFolder;
+
+fn app() -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .insert_resource(LocaleDefaultFallback::new(Some("de")))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(DiagnosticsPlugin)
+        .add_plugin(LocalizationPlugin)
+        .add_plugin(LocalizationDiagnosticsPlugin);
+
+    app
+}
+
+fn diagnostic(app: &App, id: DiagnosticId) -> &Diagnostic {
+    app.world.resource::<Diagnostics>().get(id).unwrap()
+}
+
+#[test]
+fn format_errors_are_not_counted_as_fallback_hits() {
+    let mut app = app();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+    folder
+        .insert_source("en-US", "hello = Hello { $name }!\nbye = Goodbye!")
+        .unwrap();
+    folder
+        .insert_source("de", "hello = Hallo!\nbye = Tschüss!\nthanks = Danke!")
+        .unwrap();
+    app.update();
+
+    let localizations = app.world.resource::<Localizations>();
+    // Fails in en-US, because the argument is missing
+    assert_eq!(
+        localizations.try_get_message(FOLDER_PATH, "hello").unwrap(),
+        "Hallo!"
+    );
+    // Missing in en-US
+    assert_eq!(
+        localizations
+            .try_get_message(FOLDER_PATH, "thanks")
+            .unwrap(),
+        "Danke!"
+    );
+    app.update();
+
+    let value = |id| diagnostic(&app, id).value().unwrap();
+    assert_eq!(value(LocalizationDiagnosticsPlugin::FORMAT_ERRORS), 1.0);
+    assert_eq!(value(LocalizationDiagnosticsPlugin::FALLBACK_HITS), 1.0);
+    assert_eq!(value(LocalizationDiagnosticsPlugin::FORMAT_FAILURES), 0.0);
+}
+
+#[test]
+fn long_folder_paths_have_distinct_diagnostic_names() {
+    let mut app = app();
+
+    let folder_paths = [
+        "mods/first/assets/localization/strings",
+        "mods/second/assets/localization/strings",
+    ];
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    for folder_path in folder_paths {
+        localizations.add_folder(folder_path);
+    }
+    app.update();
+
+    let names = folder_paths.map(|folder_path| {
+        let id = LocalizationDiagnosticsPlugin::bundles_diagnostic_id(folder_path);
+        diagnostic(&app, id).name.to_string()
+    });
+
+    assert_ne!(names[0], names[1]);
+
+    for name in names {
+        assert!(name.chars().count() <= bevy::diagnostic::MAX_DIAGNOSTIC_NAME_WIDTH);
+    }
+}
+
+#[test]
+fn folders_loaded_as_typed_and_runtime_localization_are_measured_once() {
+    let mut app = app();
+    app.add_localization::<DiagnosticsFolder>();
+
+    let mut localization = app.world.resource_mut::<Localization<DiagnosticsFolder>>();
+    localization
+        .insert_source("en-US", "hello = Hello!")
+        .unwrap();
+    localization.insert_source("de", "hello = Hallo!").unwrap();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+    folder.insert_source("en-US", "hello = Hello!").unwrap();
+    folder.insert_source("de", "hello = Hallo!").unwrap();
+
+    app.update();
+    app.update();
+
+    let id = LocalizationDiagnosticsPlugin::bundles_diagnostic_id(FOLDER_PATH);
+    let bundles = diagnostic(&app, id);
+    assert_eq!(bundles.measurements().count(), 2);
+    assert_eq!(bundles.value(), Some(2.0));
+}
@@ -0,0 +1,105 @@
+use std::{fs, path::Path, thread, time::Duration};
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/test"]
+struct TestLocalizationFolder;
+
+fn app(asset_folder: &Path) -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin {
+            asset_folder: asset_folder.to_string_lossy().into_owned(),
+            ..default()
+        })
+        .add_plugin(LocalizationPlugin);
+
+    app
+}
+
+fn text_value(app: &App, entity: Entity) -> String {
+    app.world
+        .get::<Text>(entity)
+        .unwrap()
+        .sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect()
+}
+
+fn is_right_to_left(app: &App) -> bool {
+    app.world.resource::<LocaleDirection>().is_right_to_left()
+}
+
+#[test]
+fn direction_changes_in_the_same_frame_as_the_text() {
+    let directory = tempfile::tempdir().unwrap();
+    let folder = directory.path().join("strings/test");
+    fs::create_dir_all(&folder).unwrap();
+    fs::write(folder.join("en-US.ftl"), "hello = Hello!").unwrap();
+    fs::write(folder.join("ar.ftl"), "hello = مرحبا!").unwrap();
+
+    let mut app = app(directory.path());
+    app.add_localization::<TestLocalizationFolder>();
+    let entity = app
+        .world
+        .spawn((
+            Text::default(),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id();
+
+    for _ in 0..500 {
+        app.update();
+
+        if text_value(&app, entity) == "Hello!" {
+            break;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert_eq!(text_value(&app, entity), "Hello!");
+    assert!(!is_right_to_left(&app));
+
+    app.world.resource_mut::<Locale>().set("ar");
+    app.update();
+
+    // The file of the new locale is still loading
+    assert_eq!(text_value(&app, entity), "Hello!");
+    assert!(!is_right_to_left(&app));
+
+    for _ in 0..500 {
+        app.update();
+
+        let switched = text_value(&app, entity) == "مرحبا!";
+        assert_eq!(is_right_to_left(&app), switched);
+
+        if switched {
+            return;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    panic!("the text never switched to the new locale");
+}
+
+#[test]
+fn direction_follows_the_locale_without_localizations() {
+    let directory = tempfile::tempdir().unwrap();
+
+    let mut app = app(directory.path());
+    app.update();
+
+    assert!(!is_right_to_left(&app));
+
+    app.world.resource_mut::<Locale>().set("he");
+    app.update();
+
+    assert!(is_right_to_left(&app));
+}
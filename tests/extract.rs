@@ -0,0 +1,283 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use bevy_mod_localization::extract::{
+    compare_with_folders, extract_from_sources, Extraction, FolderUsageReport, MessageUsage,
+};
+use common::fixture;
+
+fn extract(source: &str) -> Extraction {
+    extract_from_sources([(PathBuf::from("src/lib.rs"), source.to_string())])
+}
+
+/// The message IDs found in the source, with their folder and line.
+fn usages(source: &str) -> Vec<(String, Option<String>, usize)> {
+    let extraction = extract(source);
+    assert_eq!(extraction.errors, []);
+
+    extraction
+        .usages
+        .into_iter()
+        .map(|usage| (usage.message_id, usage.folder_path, usage.line))
+        .collect()
+}
+
+fn usage(id: &str, folder_path: Option<&str>, line: usize) -> (String, Option<String>, usize) {
+    (id.to_string(), folder_path.map(str::to_string), line)
+}
+
+const MENU_FOLDER: &str = r#"
+#[derive(LocalizationFolder)]
+#[folder_path = "/strings/menu"]
+struct MenuFolder;
+"#;
+
+#[test]
+fn comments_are_skipped() {
+    let source = r#"
+fn setup() {
+    // LocalizedText::new("line");
+    /* LocalizedText::new("block"); /* nested */ LocalizedText::new("nested"); */
+    LocalizedText::new("code");
+}
+"#;
+
+    assert_eq!(usages(source), [usage("code", None, 5)]);
+}
+
+#[test]
+fn string_escapes_are_resolved() {
+    let source = r##"
+fn setup() {
+    LocalizedText::new("quote\"d");
+    LocalizedText::new("\u{68}ello");
+    LocalizedText::new("long\
+                        -id");
+    LocalizedText::new(r#"raw "quoted" id"#);
+    LocalizedText::new(r"raw");
+}
+"##;
+
+    let ids: Vec<String> = usages(source).into_iter().map(|(id, ..)| id).collect();
+    assert_eq!(
+        ids,
+        ["quote\"d", "hello", "long-id", "raw \"quoted\" id", "raw"]
+    );
+}
+
+#[test]
+fn lines_are_counted_through_multiline_tokens() {
+    let source = "/* one\ntwo */ const S: &str = \"three\nfour\";\nfn setup() { LocalizedText::new(\"five\"); }";
+
+    assert_eq!(usages(source), [usage("five", None, 4)]);
+}
+
+#[test]
+fn localized_text_uses_the_last_segment_of_its_type_parameter() {
+    let source = r#"
+fn setup() {
+    LocalizedText::<crate::menu::MenuFolder>::new("play");
+    LocalizedText::<Unknown>::new("unknown");
+    LocalizedText::new("untyped");
+}
+"#;
+
+    assert_eq!(
+        usages(&format!("{MENU_FOLDER}{source}")),
+        [
+            usage("play", Some("strings/menu"), 7),
+            usage("unknown", None, 8),
+            usage("untyped", None, 9),
+        ]
+    );
+}
+
+#[test]
+fn runtime_folders_are_taken_from_the_call() {
+    let source = r#"
+fn setup(localizations: Res<Localizations>, folder: &str) {
+    DynamicLocalizedText::new("/mods/example", "hello");
+    localizations.try_get_message("mods/example", "bye");
+    localizations.try_format_message("mods/example", "score", args);
+    localizations.try_get_message(folder, "unknown");
+    other.try_get_message("mods/other", "undeclared");
+}
+"#;
+
+    assert_eq!(
+        usages(source),
+        [
+            usage("hello", Some("mods/example"), 3),
+            usage("bye", Some("mods/example"), 4),
+            usage("score", Some("mods/example"), 5),
+            usage("unknown", None, 6),
+            usage("undeclared", Some("mods/other"), 7),
+        ]
+    );
+}
+
+#[test]
+fn receivers_are_resolved_through_their_declarations() {
+    let source = r#"
+fn system(menu: Res<Localization<MenuFolder>>, other: &Localization<OtherFolder>) {
+    menu.try_get_message("play");
+    other.try_format_message("score", args);
+    menu.try_get_message_in(&chain, "quit");
+    unknown.try_get_message("unknown");
+
+    {
+        let menu: &Localization<OtherFolder> = &other;
+        menu.try_get_message("shadowed");
+    }
+
+    menu.try_get_message("in_scope_again");
+}
+
+fn other_system(menu: Res<Localization<OtherFolder>>) {
+    menu.try_get_message("other");
+}
+
+impl LocalizationFolder for OtherFolder {
+    const FOLDER_PATH: &'static str = "strings/other";
+}
+"#;
+
+    assert_eq!(
+        usages(&format!("{MENU_FOLDER}{source}")),
+        [
+            usage("play", Some("strings/menu"), 7),
+            usage("score", Some("strings/other"), 8),
+            usage("quit", Some("strings/menu"), 9),
+            usage("unknown", None, 10),
+            usage("shadowed", Some("strings/other"), 14),
+            usage("in_scope_again", Some("strings/menu"), 17),
+            usage("other", Some("strings/other"), 21),
+        ]
+    );
+}
+
+#[test]
+fn typed_receivers_are_not_mistaken_for_runtime_folders() {
+    let source = r#"
+fn system(menu: Res<Localization<MenuFolder>>) {
+    menu.try_format_message("greeting", "not a folder");
+}
+"#;
+
+    assert_eq!(
+        usages(&format!("{MENU_FOLDER}{source}")),
+        [usage("greeting", Some("strings/menu"), 7)]
+    );
+}
+
+#[test]
+fn fields_closures_and_macros_are_scanned() {
+    let source = r#"
+#[derive(SystemParam)]
+struct MenuText<'w> {
+    menu: Res<'w, Localization<MenuFolder>>,
+}
+
+impl MenuText<'_> {
+    fn title(&self) -> String {
+        self.menu.try_get_message("title").unwrap()
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.add(|world: &mut World| {
+        let menu = world.resource::<Localization<MenuFolder>>();
+        println!("{:?}", menu.try_get_message("untyped_let"));
+    });
+    let callback = |menu: &Localization<MenuFolder>| menu.try_get_message("closure");
+    info!("{}", format!("{:?}", LocalizedText::<MenuFolder>::new("macro")));
+}
+"#;
+
+    assert_eq!(
+        usages(&format!("{MENU_FOLDER}{source}")),
+        [
+            usage("title", Some("strings/menu"), 13),
+            usage("untyped_let", None, 20),
+            usage("closure", Some("strings/menu"), 22),
+            usage("macro", Some("strings/menu"), 23),
+        ]
+    );
+}
+
+#[test]
+fn ids_built_at_runtime_and_getters_are_ignored() {
+    let source = r#"
+fn system(menu: Res<Localization<MenuFolder>>, text: Query<&LocalizedText<MenuFolder>>) {
+    menu.try_get_message(&format!("level-{}", 1));
+    menu.try_get_message(id);
+    text.single().message_id();
+}
+"#;
+
+    assert_eq!(usages(&format!("{MENU_FOLDER}{source}")), []);
+}
+
+#[test]
+fn folder_types_are_collected() {
+    let source = r#"
+impl LocalizationFolder for OtherFolder {
+    const FOLDER_PATH: &'static str = "strings/other";
+}
+"#;
+
+    let extraction = extract(&format!("{MENU_FOLDER}{source}"));
+    assert_eq!(
+        extraction.folder_paths.into_iter().collect::<Vec<_>>(),
+        ["strings/menu", "strings/other"]
+    );
+}
+
+#[test]
+fn files_that_cannot_be_parsed_are_reported() {
+    let extraction = extract("fn setup() {\n    LocalizedText::new(\"lost\")\n");
+
+    assert_eq!(extraction.usages, []);
+    assert_eq!(extraction.errors.len(), 1);
+    assert_eq!(extraction.errors[0].path, PathBuf::from("src/lib.rs"));
+}
+
+#[test]
+fn stubs_start_on_a_new_line() {
+    let assets = fixture(&[]);
+    let folder = assets.path().join("strings/menu");
+    fs::create_dir_all(&folder).unwrap();
+    // The file doesn't end with a newline
+    fs::write(folder.join("en-US.ftl"), "play = Play").unwrap();
+
+    let extraction = extract(&format!(
+        "{MENU_FOLDER}fn setup() {{ LocalizedText::<MenuFolder>::new(\"quit\"); }}"
+    ));
+    let reports =
+        compare_with_folders(&extraction, assets.path(), &"en-US".parse().unwrap()).unwrap();
+
+    assert_eq!(
+        reports,
+        [FolderUsageReport {
+            folder_path: "strings/menu".to_string(),
+            reference_path: folder.join("en-US.ftl"),
+            missing: vec![MessageUsage {
+                message_id: "quit".to_string(),
+                folder_path: Some("strings/menu".to_string()),
+                path: PathBuf::from("src/lib.rs"),
+                line: 5,
+            }],
+            unused: vec!["play".to_string()],
+        }]
+    );
+
+    reports[0].write_stubs().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(folder.join("en-US.ftl")).unwrap(),
+        "play = Play\n\n# Used in src/lib.rs:5\nquit = quit\n"
+    );
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "ron")]
+
+use std::{fs, path::Path, thread, time::Duration};
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::{
+    locale::{LocaleFallbackConfig, LocaleFallbackConfigHandle},
+    prelude::*,
+};
+
+const FOLDER_PATH: &str = "strings/runtime";
+const CONFIG_PATH: &str = "locales.fallbacks.ron";
+
+fn app(asset_folder: &Path) -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("de-AT"))
+        .insert_resource(LocaleDefaultFallback::new(Some("en-US")))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin {
+            asset_folder: asset_folder.to_string_lossy().into_owned(),
+            watch_for_changes: true,
+        })
+        .add_plugin(LocalizationPlugin);
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+    folder.insert_source("en-US", "hello = Hello!").unwrap();
+    folder.insert_source("de", "hello = Hallo!").unwrap();
+    folder.insert_source("fr", "hello = Bonjour !").unwrap();
+
+    app
+}
+
+/// Update the app until the message is resolved to the expected text.
+fn update_until_hello_is(app: &mut App, expected: &str) {
+    for _ in 0..500 {
+        app.update();
+
+        let localizations = app.world.resource::<Localizations>();
+        if localizations
+            .try_get_message(FOLDER_PATH, "hello")
+            .as_deref()
+            == Ok(expected)
+        {
+            return;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let localizations = app.world.resource::<Localizations>();
+    panic!(
+        "expected {expected:?}, got {:?}",
+        localizations.try_get_message(FOLDER_PATH, "hello")
+    );
+}
+
+fn resolution_chain(app: &App) -> Vec<String> {
+    let localizations = app.world.resource::<Localizations>();
+    localizations
+        .get(FOLDER_PATH)
+        .unwrap()
+        .resolution_chain()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn hot_reloaded_config_recomputes_the_chain() {
+    let directory = tempfile::tempdir().unwrap();
+    let config_path = directory.path().join(CONFIG_PATH);
+    fs::write(&config_path, r#"(fallbacks: { "de-AT": ["fr"] })"#).unwrap();
+
+    let mut app = app(directory.path());
+    app.load_locale_fallbacks(CONFIG_PATH);
+
+    update_until_hello_is(&mut app, "Bonjour !");
+    // The config replaces the default fallback of the app as well
+    assert_eq!(resolution_chain(&app), ["de-AT", "fr"]);
+
+    fs::write(
+        &config_path,
+        r#"(default_fallback: Some("fr"), fallbacks: { "de-AT": ["de"] })"#,
+    )
+    .unwrap();
+
+    update_until_hello_is(&mut app, "Hallo!");
+    assert_eq!(resolution_chain(&app), ["de-AT", "de", "fr"]);
+    assert_eq!(
+        *app.world.resource::<LocaleDefaultFallback>(),
+        LocaleDefaultFallback::new(Some("fr"))
+    );
+}
+
+#[test]
+fn removed_config_restores_the_previous_fallbacks() {
+    let directory = tempfile::tempdir().unwrap();
+    fs::write(
+        directory.path().join(CONFIG_PATH),
+        r#"(default_fallback: Some("fr"))"#,
+    )
+    .unwrap();
+
+    let mut app = app(directory.path());
+    app.load_locale_fallbacks(CONFIG_PATH);
+
+    update_until_hello_is(&mut app, "Bonjour !");
+    assert_eq!(resolution_chain(&app), ["de-AT", "fr"]);
+
+    let handle = app.world.resource::<LocaleFallbackConfigHandle>().0.clone();
+    app.world
+        .resource_mut::<Assets<LocaleFallbackConfig>>()
+        .remove(&handle);
+
+    update_until_hello_is(&mut app, "Hello!");
+    assert_eq!(resolution_chain(&app), ["de-AT", "en-US"]);
+    assert_eq!(
+        *app.world.resource::<LocaleDefaultFallback>(),
+        LocaleDefaultFallback::new(Some("en-US"))
+    );
+}
@@ -0,0 +1,84 @@
+use bevy_mod_localization::prelude::*;
+use fluent::FluentArgs;
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/cache"]
+struct CacheLocalizationFolder;
+
+fn localization(ftl_string: &str, capacity: usize) -> Localization<CacheLocalizationFolder> {
+    let mut localization = Localization::new(vec!["en-US".parse().unwrap()]);
+    localization.insert_source("en-US", ftl_string).unwrap();
+    localization.enable_format_cache(capacity);
+    localization
+}
+
+fn score(localization: &Localization<CacheLocalizationFolder>, score: i32) -> String {
+    let mut args = FluentArgs::new();
+    args.set("score", score);
+    localization.try_format_message("score", args).unwrap()
+}
+
+fn hits_and_misses(localization: &Localization<CacheLocalizationFolder>) -> (u64, u64) {
+    let stats = localization.format_cache_stats().unwrap();
+    (stats.hits, stats.misses)
+}
+
+#[test]
+fn messages_with_other_arguments_are_formatted_again() {
+    let localization = localization("score = Score: { $score }", 16);
+
+    assert_eq!(score(&localization, 1), "Score: 1");
+    assert_eq!(score(&localization, 2), "Score: 2");
+    assert_eq!(score(&localization, 1), "Score: 1");
+
+    let mut args = FluentArgs::new();
+    args.set("score", "1");
+    assert_eq!(
+        localization.try_format_message("score", args).unwrap(),
+        "Score: 1"
+    );
+
+    assert_eq!(hits_and_misses(&localization), (1, 3));
+}
+
+#[test]
+fn changed_messages_are_formatted_again() {
+    let mut localization = localization("hello = Hello!\nbye = Goodbye!", 16);
+
+    assert_eq!(localization.try_get_message("hello").unwrap(), "Hello!");
+    assert_eq!(localization.try_get_message("bye").unwrap(), "Goodbye!");
+
+    localization.insert_source("en-US", "hello = Hi!").unwrap();
+
+    assert_eq!(localization.try_get_message("hello").unwrap(), "Hi!");
+    assert_eq!(localization.try_get_message("bye").unwrap(), "Goodbye!");
+    assert_eq!(hits_and_misses(&localization), (1, 3));
+}
+
+#[test]
+fn least_recently_used_messages_are_evicted() {
+    let localization = localization("a = A\nb = B\nc = C", 2);
+
+    localization.try_get_message("a").unwrap();
+    localization.try_get_message("b").unwrap();
+    localization.try_get_message("a").unwrap();
+    // Evicts `b`, which has been used less recently than `a`
+    localization.try_get_message("c").unwrap();
+    assert_eq!(hits_and_misses(&localization), (1, 3));
+
+    localization.try_get_message("a").unwrap();
+    assert_eq!(hits_and_misses(&localization), (2, 3));
+
+    localization.try_get_message("b").unwrap();
+    assert_eq!(hits_and_misses(&localization), (2, 4));
+    assert_eq!(localization.format_cache_stats().unwrap().entries, 2);
+}
+
+#[test]
+fn missing_messages_are_cached_as_errors() {
+    let localization = localization("hello = Hello!", 16);
+
+    assert!(localization.try_get_message("missing").is_err());
+    assert!(localization.try_get_message("missing").is_err());
+    assert_eq!(hits_and_misses(&localization), (1, 1));
+}
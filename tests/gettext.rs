@@ -0,0 +1,264 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use bevy_mod_localization::{
+    gettext::{ftl_to_po, po_to_ftl},
+    lint::{lint_folder, LintKind},
+};
+use common::fixture;
+use unic_langid::LanguageIdentifier;
+
+const PLURAL_REFERENCE: &str = "\
+items = { $count ->
+    [one] One item
+   *[other] { $count } items
+}
+";
+
+fn locale(locale: &str) -> LanguageIdentifier {
+    locale.parse().unwrap()
+}
+
+/// Export the source as translation of itself and import it again.
+fn round_trip(source: &str, locale: &LanguageIdentifier) -> String {
+    po_to_ftl(&ftl_to_po(source, source, locale), locale).unwrap()
+}
+
+#[test]
+fn russian_plurals_keep_a_variant_for_fractions() {
+    let translation = "\
+items = { $count ->
+    [one] { $count } предмет
+    [few] { $count } предмета
+    [many] { $count } предметов
+   *[other] { $count } предмета
+}
+";
+
+    let po = ftl_to_po(PLURAL_REFERENCE, translation, &locale("ru"));
+    assert!(po.contains("msgstr[2] \"{ $count } предметов\"\n"));
+    assert!(!po.contains("msgstr[3]"));
+
+    assert_eq!(
+        po_to_ftl(&po, &locale("ru")).unwrap(),
+        "\
+items =
+    { $count ->
+        [one] { $count } предмет
+        [few] { $count } предмета
+        [many] { $count } предметов
+       *[other] { $count } предметов
+    }
+"
+    );
+}
+
+#[test]
+fn polish_plurals_round_trip() {
+    let source = "\
+items =
+    { $count ->
+        [one] { $count } przedmiot
+        [few] { $count } przedmioty
+        [many] { $count } przedmiotów
+       *[other] { $count } przedmiotów
+    }
+";
+
+    assert_eq!(round_trip(source, &locale("pl")), source);
+}
+
+#[test]
+fn arabic_plurals_round_trip() {
+    let source = "\
+items =
+    { $count ->
+        [zero] لا عناصر
+        [one] عنصر واحد
+        [two] عنصران
+        [few] { $count } عناصر
+        [many] { $count } عنصرًا
+       *[other] { $count } عنصر
+    }
+";
+
+    let po = ftl_to_po(source, source, &locale("ar"));
+    assert!(po.contains("msgstr[5] \"{ $count } عنصر\"\n"));
+
+    assert_eq!(po_to_ftl(&po, &locale("ar")).unwrap(), source);
+}
+
+#[test]
+fn plurals_without_known_forms_are_exported_as_fluent_source() {
+    let source = "\
+items =
+    { $count ->
+        [one] { $count } mhír
+        [two] { $count } mhír
+        [few] { $count } mhír
+        [many] { $count } mír
+       *[other] { $count } mír
+    }
+";
+
+    let po = ftl_to_po(PLURAL_REFERENCE, source, &locale("ga"));
+    assert!(!po.contains("Plural-Forms"));
+    assert!(!po.contains("msgid_plural"));
+    assert!(po.contains("msgstr \"\"\n\"{ $count ->\\n\"\n"));
+
+    assert_eq!(round_trip(source, &locale("ga")), source);
+}
+
+#[test]
+fn imported_plurals_have_every_plural_category() {
+    let translation = "\
+items = { $count ->
+    [zero] zero
+    [one] one
+    [two] two
+    [few] few
+    [many] many
+   *[other] other
+}
+";
+
+    for language in [
+        "de", "fr", "ja", "ru", "uk", "be", "pl", "cs", "sk", "ar", "ga",
+    ] {
+        let po = ftl_to_po(PLURAL_REFERENCE, translation, &locale(language));
+        let ftl = po_to_ftl(&po, &locale(language)).unwrap();
+
+        let folder = fixture(&[
+            ("en-US.ftl", PLURAL_REFERENCE),
+            (&format!("{language}.ftl"), &ftl),
+        ]);
+
+        let diagnostics = lint_folder(folder.path(), &locale("en-US")).unwrap();
+
+        assert!(
+            diagnostics.iter().all(|diagnostic| !matches!(
+                diagnostic.kind,
+                LintKind::MissingPluralCategories { .. }
+            )),
+            "{language}: {diagnostics:?}"
+        );
+    }
+}
+
+#[test]
+fn fuzzy_and_untranslated_entries_are_skipped() {
+    let po = r#"msgid ""
+msgstr ""
+"Language: de\n"
+
+#, fuzzy
+msgctxt "hello"
+msgid "Hello!"
+msgstr "Hallo!"
+
+msgctxt "bye"
+msgid "Bye!"
+msgstr ""
+
+msgctxt "play"
+msgid "Play"
+msgstr "Spielen"
+"#;
+
+    assert_eq!(po_to_ftl(po, &locale("de")).unwrap(), "play = Spielen\n");
+}
+
+#[test]
+fn multiline_selections_round_trip() {
+    let source = "\
+greeting =
+    { $gender ->
+        [female]
+            Willkommen,
+            liebe { $name }!
+       *[other] Willkommen, { $name }!
+    }
+";
+
+    let po = ftl_to_po(source, source, &locale("de"));
+    assert!(po.contains("msgid \"\"\n\"{ $gender ->\\n\"\n"));
+
+    assert_eq!(round_trip(source, &locale("de")), source);
+}
+
+#[test]
+fn attribute_only_messages_round_trip() {
+    let source = "\
+# The login form
+login =
+    .title = Anmelden
+    .placeholder = Name
+";
+
+    let po = ftl_to_po(source, source, &locale("de"));
+    assert_eq!(po.matches("#. The login form\n").count(), 1);
+    assert!(po.contains("#. The login form\nmsgctxt \"login.title\"\n"));
+
+    assert_eq!(round_trip(source, &locale("de")), source);
+}
+
+#[test]
+fn terms_round_trip() {
+    let source = "\
+-brand = Bevy
+    .gender = neuter
+play = Spiele { -brand }
+";
+
+    let po = ftl_to_po(source, source, &locale("de"));
+    assert!(po.contains("msgctxt \"-brand\"\n"));
+    assert!(po.contains("msgctxt \"-brand.gender\"\n"));
+
+    assert_eq!(round_trip(source, &locale("de")), source);
+}
+
+#[test]
+fn escapes_round_trip() {
+    let source = "quote = Sag \"Hallo\" mit \\ und\tTab { \"{\" }\n";
+
+    let po = ftl_to_po(source, source, &locale("de"));
+    assert!(po.contains(r#"msgstr "Sag \"Hallo\" mit \\ und\tTab { \"{\" }""#));
+
+    assert_eq!(round_trip(source, &locale("de")), source);
+}
+
+#[test]
+fn carriage_returns_are_escaped() {
+    let source = "return = Zeile\rWagenrücklauf\n";
+
+    let po = ftl_to_po(source, source, &locale("de"));
+    assert!(po.contains(r#"msgstr "Zeile\rWagenrücklauf""#));
+
+    assert_eq!(round_trip(source, &locale("de")), source);
+}
+
+#[test]
+fn comments_without_entry_round_trip() {
+    let source = "\
+### The main menu
+
+## Buttons
+
+# The button to start the game
+play = Spielen
+    .tooltip = Startet das Spiel
+
+# TODO: more buttons
+
+quit = Beenden
+
+# The end
+";
+
+    let po = ftl_to_po(source, source, &locale("de"));
+    assert!(po.contains("#. Resource comment: The main menu\n#. Group comment: Buttons\n"));
+    assert!(po.starts_with("#. Comment: The end\nmsgid \"\"\n"));
+
+    assert_eq!(round_trip(source, &locale("de")), source);
+}
@@ -0,0 +1,99 @@
+use bevy::text::Font;
+use bevy_mod_localization::glyphs::{find_missing_glyphs, MissingGlyphs};
+
+fn font() -> Font {
+    let bytes = std::fs::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/fonts/FiraSans-Bold.ttf"
+    ))
+    .unwrap();
+    Font::try_from_bytes(bytes).unwrap()
+}
+
+/// The IDs and attributes of the messages with missing glyphs, with the missing characters.
+fn missing(ftl_string: &str) -> Vec<(String, Option<String>, String)> {
+    find_missing_glyphs(&font(), &"zh".parse().unwrap(), ftl_string)
+        .into_iter()
+        .map(
+            |MissingGlyphs {
+                 message_id,
+                 attribute,
+                 characters,
+                 ..
+             }| (message_id, attribute, characters.into_iter().collect()),
+        )
+        .collect()
+}
+
+fn entry(
+    message_id: &str,
+    attribute: Option<&str>,
+    characters: &str,
+) -> (String, Option<String>, String) {
+    (
+        message_id.to_string(),
+        attribute.map(str::to_string),
+        characters.to_string(),
+    )
+}
+
+#[test]
+fn covered_text_has_no_missing_glyphs() {
+    assert_eq!(
+        missing("hello = Hello { $name }!\n    .title = Welcome"),
+        []
+    );
+}
+
+#[test]
+fn every_variant_of_a_selection_is_checked() {
+    let ftl_string = "\
+items = { $count ->
+    [one] 一个
+   *[other] { $count } 个
+}
+";
+
+    assert_eq!(missing(ftl_string), [entry("items", None, "一个")]);
+}
+
+#[test]
+fn displayed_literals_are_checked_but_arguments_are_not() {
+    let ftl_string = "\
+quote = { \"\\u4F60\" } { 42 }
+date = { DATETIME($date, month: \"好\") }
+";
+
+    assert_eq!(missing(ftl_string), [entry("quote", None, "你")]);
+}
+
+#[test]
+fn terms_are_checked_where_they_are_defined() {
+    let ftl_string = "\
+-brand = 世界
+    .gender = 中
+hello = Hello { -brand }!
+";
+
+    assert_eq!(
+        missing(ftl_string),
+        [
+            entry("-brand", None, "世界"),
+            entry("-brand", Some("gender"), "中")
+        ]
+    );
+}
+
+#[test]
+fn invalid_entries_are_skipped() {
+    let ftl_string = "\
+hello = 你
+broken = { $unclosed
+bye = 好
+";
+
+    assert_eq!(
+        missing(ftl_string),
+        [entry("hello", None, "你"), entry("bye", None, "好")]
+    );
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use bevy_mod_localization::lint::{lint_folder, LintDiagnostic, LintKind, Severity};
+use common::fixture;
+
+fn lint(files: &[(&str, &str)]) -> Vec<LintDiagnostic> {
+    let folder = fixture(files);
+    lint_folder(folder.path(), &"en-US".parse().unwrap()).unwrap()
+}
+
+fn kinds(diagnostics: &[LintDiagnostic]) -> Vec<&LintKind> {
+    diagnostics
+        .iter()
+        .map(|diagnostic| &diagnostic.kind)
+        .collect()
+}
+
+#[test]
+fn consistent_folders_have_no_diagnostics() {
+    let diagnostics = lint(&[
+        ("en-US.ftl", "hello = Hello { $name }!"),
+        ("de.ftl", "hello = Hallo { $name }!"),
+    ]);
+
+    assert_eq!(diagnostics, []);
+}
+
+#[test]
+fn syntax_errors_are_located() {
+    let diagnostics = lint(&[("en-US.ftl", "hello = Hello!\n= No ID\nbye = Bye!")]);
+
+    assert!(matches!(
+        kinds(&diagnostics)[..],
+        [LintKind::SyntaxError { .. }]
+    ));
+    assert_eq!(diagnostics[0].line, Some(2));
+    assert_eq!(diagnostics[0].severity(), Severity::Error);
+}
+
+#[test]
+fn non_canonical_file_names_get_a_suggestion() {
+    let diagnostics = lint(&[
+        ("en-US.ftl", "hello = Hello!"),
+        ("de-de.ftl", "hello = Hallo!"),
+    ]);
+
+    assert_eq!(
+        kinds(&diagnostics),
+        [&LintKind::InvalidFileName {
+            suggestion: Some("de-DE".to_string())
+        }]
+    );
+}
+
+#[test]
+fn duplicate_ids_point_to_the_second_definition() {
+    let diagnostics = lint(&[("en-US.ftl", "hello = Hello!\nbye = Bye!\nhello = Hi!")]);
+
+    assert_eq!(
+        kinds(&diagnostics),
+        [&LintKind::DuplicateId {
+            id: "hello".to_string()
+        }]
+    );
+    assert_eq!(diagnostics[0].line, Some(3));
+}
+
+#[test]
+fn a_missing_reference_locale_is_an_error() {
+    let diagnostics = lint(&[("de.ftl", "hello = Hallo!")]);
+
+    assert_eq!(
+        kinds(&diagnostics),
+        [&LintKind::MissingReferenceLocale {
+            locale: "en-US".parse().unwrap()
+        }]
+    );
+    assert_eq!(diagnostics[0].severity(), Severity::Error);
+}
+
+#[test]
+fn missing_and_extra_messages_are_warnings() {
+    let diagnostics = lint(&[
+        ("en-US.ftl", "hello = Hello!\nbye = Bye!"),
+        ("de.ftl", "hello = Hallo!\nthanks = Danke!"),
+    ]);
+
+    assert_eq!(
+        kinds(&diagnostics),
+        [
+            &LintKind::MissingMessage {
+                id: "bye".to_string()
+            },
+            &LintKind::ExtraMessage {
+                id: "thanks".to_string()
+            },
+        ]
+    );
+    assert!(diagnostics
+        .iter()
+        .all(|diagnostic| diagnostic.severity() == Severity::Warning));
+}
+
+#[test]
+fn mismatched_variables_list_the_differences() {
+    let diagnostics = lint(&[
+        ("en-US.ftl", "hello = Hello { $name }!"),
+        ("de.ftl", "hello = Hallo { $nmae }!"),
+    ]);
+
+    assert_eq!(
+        kinds(&diagnostics),
+        [&LintKind::MismatchedVariables {
+            id: "hello".to_string(),
+            missing: vec!["name".to_string()],
+            extra: vec!["nmae".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn plural_selections_need_every_category_of_the_locale() {
+    let diagnostics = lint(&[
+        (
+            "en-US.ftl",
+            "apples = { $count ->\n    [one] One apple\n   *[other] { $count } apples\n}",
+        ),
+        (
+            "ru.ftl",
+            "apples = { $count ->\n    [one] { $count } яблоко\n   *[other] { $count } яблок\n}",
+        ),
+    ]);
+
+    assert_eq!(
+        kinds(&diagnostics),
+        [&LintKind::MissingPluralCategories {
+            id: "apples".to_string(),
+            categories: vec!["few".to_string(), "many".to_string()],
+        }]
+    );
+}
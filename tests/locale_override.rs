@@ -0,0 +1,124 @@
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/test"]
+struct TestLocalizationFolder;
+
+fn app() -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin)
+        .add_localization::<TestLocalizationFolder>();
+
+    let mut localization = app
+        .world
+        .resource_mut::<Localization<TestLocalizationFolder>>();
+    localization
+        .insert_source("en-US", "hello = Hello!")
+        .unwrap();
+    localization.insert_source("de", "hello = Hallo!").unwrap();
+
+    app
+}
+
+fn spawn_text(app: &mut App) -> Entity {
+    app.world
+        .spawn((
+            Text::default(),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id()
+}
+
+fn text_value(app: &App, entity: Entity) -> String {
+    app.world
+        .get::<Text>(entity)
+        .unwrap()
+        .sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect()
+}
+
+#[test]
+fn override_is_inherited_from_the_closest_ancestor() {
+    let mut app = app();
+
+    let text = spawn_text(&mut app);
+    let inner = app
+        .world
+        .spawn(LocaleOverride::new("de"))
+        .push_children(&[text])
+        .id();
+    let outer = app
+        .world
+        .spawn(LocaleOverride::new("en-US"))
+        .push_children(&[inner])
+        .id();
+    let outer_text = spawn_text(&mut app);
+    app.world.entity_mut(outer).push_children(&[outer_text]);
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hallo!");
+    assert_eq!(text_value(&app, outer_text), "Hello!");
+
+    app.world
+        .get_mut::<LocaleOverride>(outer)
+        .unwrap()
+        .set("de");
+    app.world
+        .get_mut::<LocaleOverride>(inner)
+        .unwrap()
+        .set("en-US");
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hello!");
+    assert_eq!(text_value(&app, outer_text), "Hallo!");
+}
+
+#[test]
+fn reparented_text_follows_the_new_hierarchy() {
+    let mut app = app();
+
+    let text = spawn_text(&mut app);
+    let container = app.world.spawn_empty().push_children(&[text]).id();
+    let overridden = app.world.spawn(LocaleOverride::new("de")).id();
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hello!");
+
+    // Moving an ancestor moves the text along with it
+    app.world.entity_mut(overridden).push_children(&[container]);
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hallo!");
+
+    app.world.entity_mut(container).remove_parent();
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hello!");
+}
+
+#[test]
+fn removed_override_falls_back_to_the_global_locale() {
+    let mut app = app();
+
+    let text = spawn_text(&mut app);
+    let overridden = app
+        .world
+        .spawn(LocaleOverride::new("de"))
+        .push_children(&[text])
+        .id();
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hallo!");
+
+    app.world.entity_mut(overridden).remove::<LocaleOverride>();
+    app.update();
+
+    assert_eq!(text_value(&app, text), "Hello!");
+}
@@ -0,0 +1,258 @@
+use std::error::Error;
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::{prelude::*, LocalizationError};
+
+const FOLDER_PATH: &str = "strings/runtime";
+
+fn app() -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin);
+
+    app
+}
+
+#[test]
+fn folders_can_be_added_and_removed() {
+    let mut app = app();
+    let mut localizations = app.world.resource_mut::<Localizations>();
+
+    assert!(localizations.add_folder(FOLDER_PATH));
+    assert!(!localizations.add_folder(FOLDER_PATH));
+    assert!(localizations.contains_folder(FOLDER_PATH));
+    assert_eq!(
+        localizations.folder_paths().collect::<Vec<_>>(),
+        [FOLDER_PATH]
+    );
+
+    assert!(localizations.remove_folder(FOLDER_PATH));
+    assert!(!localizations.remove_folder(FOLDER_PATH));
+    assert!(!localizations.contains_folder(FOLDER_PATH));
+}
+
+#[test]
+fn runtime_folder_follows_the_locale() {
+    let mut app = app();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+    folder.insert_source("en-US", "hello = Hello!").unwrap();
+    folder.insert_source("de", "hello = Hallo!").unwrap();
+    app.update();
+
+    let localizations = app.world.resource::<Localizations>();
+    assert_eq!(
+        localizations.try_get_message(FOLDER_PATH, "hello").unwrap(),
+        "Hello!"
+    );
+
+    *app.world.resource_mut::<Locale>() = Locale::new("de");
+    app.update();
+    app.update();
+
+    let localizations = app.world.resource::<Localizations>();
+    assert_eq!(
+        localizations.try_get_message(FOLDER_PATH, "hello").unwrap(),
+        "Hallo!"
+    );
+}
+
+#[test]
+fn removed_folder_has_no_messages() {
+    let mut app = app();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+    localizations
+        .get_mut(FOLDER_PATH)
+        .unwrap()
+        .insert_source("en-US", "hello = Hello!")
+        .unwrap();
+    app.update();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.remove_folder(FOLDER_PATH);
+
+    assert!(localizations.try_get_message(FOLDER_PATH, "hello").is_err());
+}
+
+#[test]
+fn inserting_a_source_for_an_invalid_locale_is_an_error() {
+    let mut app = app();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+
+    let err = folder
+        .insert_source("not a locale", "hello = Hello!")
+        .unwrap_err();
+    assert!(
+        matches!(&err, LocalizationError::InvalidLocale(err) if err.locale() == "not a locale")
+    );
+    assert!(err.source().is_some());
+    assert!(!folder.remove_sources("not a locale"));
+}
+
+#[test]
+fn inserting_a_source_with_syntax_errors_is_an_error() {
+    let mut app = app();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+
+    let err = folder
+        .insert_source("en-US", "hello = Hello!\nbroken = { $unclosed")
+        .unwrap_err();
+    assert!(matches!(&err, LocalizationError::InvalidSource(errors) if errors.len() == 1));
+    assert!(err.source().is_some());
+}
+
+#[test]
+fn messages_of_missing_folders_are_an_error() {
+    let app = app();
+
+    assert_eq!(
+        app.world
+            .resource::<Localizations>()
+            .try_get_message(FOLDER_PATH, "hello"),
+        Err(LocalizationError::FolderNotFound)
+    );
+}
+
+#[test]
+fn files_with_syntax_errors_keep_their_valid_messages() {
+    let mut app = app();
+
+    app.world
+        .resource_mut::<Localizations>()
+        .add_embedded_folder(
+            FOLDER_PATH,
+            &[("en-US", "hello = Hello!\nbroken = { $unclosed\nbye = Bye!")],
+        );
+    app.update();
+
+    let localizations = app.world.resource::<Localizations>();
+    assert_eq!(
+        localizations.try_get_message(FOLDER_PATH, "hello").unwrap(),
+        "Hello!"
+    );
+    assert_eq!(
+        localizations.try_get_message(FOLDER_PATH, "bye").unwrap(),
+        "Bye!"
+    );
+    assert!(localizations
+        .try_get_message(FOLDER_PATH, "broken")
+        .is_err());
+}
+
+#[test]
+fn preload_all_discovers_the_files_of_folders_with_a_leading_slash() {
+    let mut app = app();
+    app.insert_resource(LocalizationLoadPolicy::PreloadAll);
+
+    app.world
+        .resource_mut::<Localizations>()
+        .add_folder("/strings/locale_fallback");
+
+    let de = ["de".parse().unwrap()];
+    let mut message = Err(LocalizationError::MessageNotFound);
+
+    // The files are loaded asynchronously
+    for _ in 0..200 {
+        app.update();
+
+        message = app
+            .world
+            .resource::<Localizations>()
+            .get("/strings/locale_fallback")
+            .unwrap()
+            .try_get_message_in(&de, "third");
+
+        if message.is_ok() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert_eq!(message.unwrap(), "Third message, default fallback (de)");
+}
+
+#[test]
+fn locale_switches_without_the_time_plugin() {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugin(TaskPoolPlugin::default())
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin);
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(FOLDER_PATH);
+
+    let folder = localizations.get_mut(FOLDER_PATH).unwrap();
+    folder.insert_source("en-US", "hello = Hello!").unwrap();
+    folder.insert_source("de", "hello = Hallo!").unwrap();
+    app.update();
+
+    *app.world.resource_mut::<Locale>() = Locale::new("de");
+    app.update();
+    app.update();
+
+    let localizations = app.world.resource::<Localizations>();
+    assert_eq!(
+        localizations.try_get_message(FOLDER_PATH, "hello").unwrap(),
+        "Hallo!"
+    );
+}
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/locale_fallback"]
+struct LocaleFallbackFolder;
+
+#[test]
+fn loaded_files_reach_every_folder_using_them() {
+    let mut app = app();
+    app.add_localization::<LocaleFallbackFolder>();
+
+    app.world
+        .resource_mut::<Localizations>()
+        .add_folder("strings/locale_fallback");
+
+    let expected = "First message, selected locale (en-US)";
+    let mut messages = (
+        Err(LocalizationError::MessageNotFound),
+        Err(LocalizationError::MessageNotFound),
+    );
+
+    // The files are loaded asynchronously
+    for _ in 0..200 {
+        app.update();
+
+        messages = (
+            app.world
+                .resource::<Localization<LocaleFallbackFolder>>()
+                .try_get_message("first"),
+            app.world
+                .resource::<Localizations>()
+                .try_get_message("strings/locale_fallback", "first"),
+        );
+
+        if messages.0.is_ok() && messages.1.is_ok() {
+            break;
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+
+    assert_eq!(messages.0.unwrap(), expected);
+    assert_eq!(messages.1.unwrap(), expected);
+}
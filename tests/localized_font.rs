@@ -0,0 +1,115 @@
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/test"]
+struct TestLocalizationFolder;
+
+const DEFAULT_FONT: &str = "fonts/Default.ttf";
+const JAPANESE_FONT: &str = "fonts/Japanese.ttf";
+const ARABIC_FONT: &str = "fonts/Arabic.ttf";
+
+fn app() -> App {
+    let mut localized_font = LocalizedFont::new();
+    localized_font.set_default(DEFAULT_FONT);
+    localized_font.insert_locale("ja", JAPANESE_FONT);
+    localized_font
+        .try_insert_script("Arab", ARABIC_FONT)
+        .unwrap();
+
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .insert_resource(localized_font)
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin)
+        .add_localization::<TestLocalizationFolder>();
+
+    let mut localization = app
+        .world
+        .resource_mut::<Localization<TestLocalizationFolder>>();
+    localization
+        .insert_source("en-US", "hello = Hello!")
+        .unwrap();
+    localization
+        .insert_source("ja-JP", "hello = こんにちは!")
+        .unwrap();
+    localization.insert_source("ar", "hello = مرحبا!").unwrap();
+
+    app
+}
+
+fn section(app: &App, entity: Entity) -> &TextSection {
+    &app.world.get::<Text>(entity).unwrap().sections[0]
+}
+
+fn font(app: &App, font_path: &str) -> Handle<Font> {
+    app.world.resource::<AssetServer>().get_handle(font_path)
+}
+
+/// Update the app until the text shows the message.
+fn update_until(app: &mut App, entity: Entity, message: &str) {
+    for _ in 0..10 {
+        app.update();
+
+        if section(app, entity).value == message {
+            return;
+        }
+    }
+
+    panic!("the text never showed `{message}`");
+}
+
+#[test]
+fn the_font_is_chosen_by_locale_and_script() {
+    let mut app = app();
+
+    let entity = app
+        .world
+        .spawn((
+            Text::default(),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id();
+    update_until(&mut app, entity, "Hello!");
+
+    assert_eq!(section(&app, entity).style.font, font(&app, DEFAULT_FONT));
+    assert_eq!(
+        app.world.resource::<LocalizedFont>().font(),
+        Some(&font(&app, DEFAULT_FONT))
+    );
+
+    // The font of `ja` is used for `ja-JP` as well
+    app.world.resource_mut::<Locale>().set("ja-JP");
+    update_until(&mut app, entity, "こんにちは!");
+
+    assert_eq!(section(&app, entity).style.font, font(&app, JAPANESE_FONT));
+
+    // Arabic is written in the Arabic script
+    app.world.resource_mut::<Locale>().set("ar");
+    update_until(&mut app, entity, "مرحبا!");
+
+    assert_eq!(section(&app, entity).style.font, font(&app, ARABIC_FONT));
+}
+
+#[test]
+fn the_font_switches_back_with_the_locale() {
+    let mut app = app();
+
+    let entity = app
+        .world
+        .spawn((
+            Text::from_section("", TextStyle::default()),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id();
+
+    app.world.resource_mut::<Locale>().set("ja-JP");
+    update_until(&mut app, entity, "こんにちは!");
+    assert_eq!(section(&app, entity).style.font, font(&app, JAPANESE_FONT));
+
+    app.world.resource_mut::<Locale>().set("en-US");
+    update_until(&mut app, entity, "Hello!");
+    assert_eq!(section(&app, entity).style.font, font(&app, DEFAULT_FONT));
+}
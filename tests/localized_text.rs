@@ -0,0 +1,100 @@
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/test"]
+struct TestLocalizationFolder;
+
+/// Create an app with the given FTL string as in-memory localization source.
+fn app_with_source(ftl_string: &str) -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin)
+        .add_localization::<TestLocalizationFolder>();
+
+    app.world
+        .resource_mut::<Localization<TestLocalizationFolder>>()
+        .insert_source("en-US", ftl_string)
+        .unwrap();
+
+    app
+}
+
+fn text_value(app: &App, entity: Entity) -> String {
+    app.world
+        .get::<Text>(entity)
+        .unwrap()
+        .sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect()
+}
+
+#[test]
+fn spawned_text_is_localized_in_the_same_frame() {
+    let mut app = app_with_source("hello = Hello World!\nbye = Goodbye!");
+
+    // Let the localization settle, so that it isn't changed anymore
+    app.update();
+    app.update();
+
+    let entity = app
+        .world
+        .spawn((
+            Text::from_section("placeholder", TextStyle::default()),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id();
+    app.update();
+
+    assert_eq!(text_value(&app, entity), "Hello World!");
+}
+
+#[test]
+fn changed_text_is_localized_in_the_same_frame() {
+    let mut app = app_with_source("hello = Hello World!\nbye = Goodbye!");
+
+    let entity = app
+        .world
+        .spawn((
+            Text::default(),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id();
+    app.update();
+    app.update();
+
+    assert_eq!(text_value(&app, entity), "Hello World!");
+
+    *app.world
+        .get_mut::<LocalizedText<TestLocalizationFolder>>(entity)
+        .unwrap() = LocalizedText::new("bye");
+    app.update();
+
+    assert_eq!(text_value(&app, entity), "Goodbye!");
+}
+
+#[test]
+fn spawned_dynamic_text_is_localized_in_the_same_frame() {
+    let mut app = app_with_source("");
+
+    app.world
+        .resource_mut::<Localizations>()
+        .add_embedded_folder("strings/dynamic", &[("en-US", "hello = Hello Dynamic!")]);
+    app.update();
+    app.update();
+
+    let entity = app
+        .world
+        .spawn((
+            Text::default(),
+            DynamicLocalizedText::new("strings/dynamic", "hello"),
+        ))
+        .id();
+    app.update();
+
+    assert_eq!(text_value(&app, entity), "Hello Dynamic!");
+}
@@ -0,0 +1,124 @@
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+#[derive(LocalizationFolder)]
+#[folder_path = "strings/test"]
+struct TestLocalizationFolder;
+
+const DYNAMIC_FOLDER_PATH: &str = "strings/dynamic";
+
+fn app() -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin)
+        .add_localization::<TestLocalizationFolder>();
+
+    app
+}
+
+fn text_value(app: &App, entity: Entity) -> String {
+    app.world
+        .get::<Text>(entity)
+        .unwrap()
+        .sections
+        .iter()
+        .map(|section| section.value.as_str())
+        .collect()
+}
+
+/// Replace the text, so that it can be detected whether the text is updated again.
+fn mark_stale(app: &mut App, entities: &[Entity]) {
+    for entity in entities {
+        app.world.get_mut::<Text>(*entity).unwrap().sections[0].value = "stale".to_string();
+    }
+}
+
+#[test]
+fn unrelated_message_changes_do_not_touch_text() {
+    let mut app = app();
+    app.world
+        .resource_mut::<Localization<TestLocalizationFolder>>()
+        .insert_source(
+            "en-US",
+            "hello = Hello { -name }!\nbye = Goodbye!\n-name = World",
+        )
+        .unwrap();
+
+    let hello = app
+        .world
+        .spawn((
+            Text::default(),
+            LocalizedText::<TestLocalizationFolder>::new("hello"),
+        ))
+        .id();
+    let bye = app
+        .world
+        .spawn((
+            Text::default(),
+            LocalizedText::<TestLocalizationFolder>::new("bye"),
+        ))
+        .id();
+    app.update();
+    app.update();
+
+    mark_stale(&mut app, &[hello, bye]);
+
+    app.world
+        .resource_mut::<Localization<TestLocalizationFolder>>()
+        .insert_source("en-US", "-name = Bevy")
+        .unwrap();
+    app.update();
+
+    assert_eq!(text_value(&app, hello), "Hello Bevy!");
+    assert_eq!(text_value(&app, bye), "stale");
+}
+
+#[test]
+fn unrelated_message_changes_do_not_touch_dynamic_text() {
+    let mut app = app();
+
+    let mut localizations = app.world.resource_mut::<Localizations>();
+    localizations.add_folder(DYNAMIC_FOLDER_PATH);
+    localizations
+        .get_mut(DYNAMIC_FOLDER_PATH)
+        .unwrap()
+        .insert_source(
+            "en-US",
+            "hello = Hello { -name }!\nbye = Goodbye!\n-name = World",
+        )
+        .unwrap();
+
+    let hello = app
+        .world
+        .spawn((
+            Text::default(),
+            DynamicLocalizedText::new(DYNAMIC_FOLDER_PATH, "hello"),
+        ))
+        .id();
+    let bye = app
+        .world
+        .spawn((
+            Text::default(),
+            DynamicLocalizedText::new(DYNAMIC_FOLDER_PATH, "bye"),
+        ))
+        .id();
+    app.update();
+    app.update();
+
+    assert_eq!(text_value(&app, hello), "Hello World!");
+    mark_stale(&mut app, &[hello, bye]);
+
+    app.world
+        .resource_mut::<Localizations>()
+        .get_mut(DYNAMIC_FOLDER_PATH)
+        .unwrap()
+        .insert_source("en-US", "-name = Bevy")
+        .unwrap();
+    app.update();
+
+    assert_eq!(text_value(&app, hello), "Hello Bevy!");
+    assert_eq!(text_value(&app, bye), "stale");
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "bevy_ui")]
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::{mirror::MirrorForRtl, prelude::*};
+
+fn app() -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin);
+
+    app
+}
+
+/// Update the app until the entity is mirrored or not.
+fn update_until_mirrored(app: &mut App, entity: Entity, mirrored: bool) {
+    for _ in 0..10 {
+        app.update();
+
+        if app.world.get::<MirrorForRtl>(entity).unwrap().is_mirrored() == mirrored {
+            return;
+        }
+    }
+
+    panic!("the entity was never mirrored = {mirrored}");
+}
+
+#[test]
+fn layouts_are_mirrored_for_rtl_locales_and_restored_afterwards() {
+    let mut app = app();
+
+    let row = app
+        .world
+        .spawn((
+            Style {
+                flex_direction: FlexDirection::Row,
+                margin: UiRect {
+                    left: Val::Px(10.0),
+                    right: Val::Px(20.0),
+                    ..default()
+                },
+                ..default()
+            },
+            Text::default().with_alignment(TextAlignment::Left),
+            MirrorForRtl::default(),
+        ))
+        .id();
+    let column = app
+        .world
+        .spawn((
+            Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            MirrorForRtl::default(),
+        ))
+        .id();
+    app.update();
+
+    assert!(!app.world.get::<MirrorForRtl>(row).unwrap().is_mirrored());
+
+    app.world.resource_mut::<Locale>().set("ar");
+    update_until_mirrored(&mut app, row, true);
+
+    let style = app.world.get::<Style>(row).unwrap();
+    assert_eq!(style.flex_direction, FlexDirection::RowReverse);
+    assert_eq!(style.margin.left, Val::Px(20.0));
+    assert_eq!(style.margin.right, Val::Px(10.0));
+    assert_eq!(
+        app.world.get::<Text>(row).unwrap().alignment,
+        TextAlignment::Right
+    );
+    assert_eq!(
+        app.world.get::<Style>(column).unwrap().align_items,
+        AlignItems::FlexEnd
+    );
+
+    app.world.resource_mut::<Locale>().set("en-US");
+    update_until_mirrored(&mut app, row, false);
+
+    let style = app.world.get::<Style>(row).unwrap();
+    assert_eq!(style.flex_direction, FlexDirection::Row);
+    assert_eq!(style.margin.left, Val::Px(10.0));
+    assert_eq!(style.margin.right, Val::Px(20.0));
+    assert_eq!(
+        app.world.get::<Text>(row).unwrap().alignment,
+        TextAlignment::Left
+    );
+    assert_eq!(
+        app.world.get::<Style>(column).unwrap().align_items,
+        AlignItems::FlexStart
+    );
+}
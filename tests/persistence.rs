@@ -0,0 +1,137 @@
+#![cfg(feature = "ron")]
+
+use std::fs;
+
+use bevy::{asset::AssetPlugin, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+fn app_with(persistence: LocalePersistence) -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .insert_resource(persistence)
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin);
+
+    app
+}
+
+fn fallback_map() -> LocaleFallbackMap {
+    let mut fallback_map = LocaleFallbackMap::new();
+    fallback_map.insert("de-AT", vec!["de-DE", "de"]);
+    // Locales without fallbacks are saved as well
+    fallback_map.insert("en-GB", Vec::<&str>::new());
+    fallback_map.set_transitive(true);
+    fallback_map
+}
+
+#[test]
+fn saved_settings_are_restored_at_startup() {
+    let directory = tempfile::tempdir().unwrap();
+
+    let mut app = app_with(LocalePersistence::new(directory.path()));
+    app.update();
+
+    app.world.resource_mut::<Locale>().set("de-AT");
+    app.insert_resource(fallback_map());
+    app.insert_resource(LocaleDefaultFallback::new(Some("de")));
+    app.update();
+
+    let app = app_with(LocalePersistence::new(directory.path()));
+
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("de-AT"));
+    assert_eq!(*app.world.resource::<LocaleFallbackMap>(), fallback_map());
+    assert_eq!(
+        *app.world.resource::<LocaleDefaultFallback>(),
+        LocaleDefaultFallback::new(Some("de"))
+    );
+}
+
+#[test]
+fn unchanged_settings_are_not_saved() {
+    let directory = tempfile::tempdir().unwrap();
+    let persistence = LocalePersistence::new(directory.path());
+    let path = persistence.path();
+
+    let mut app = app_with(persistence);
+    app.update();
+    app.update();
+
+    assert!(!path.exists());
+}
+
+#[test]
+fn settings_changed_on_the_first_frame_are_saved() {
+    let directory = tempfile::tempdir().unwrap();
+    let persistence = LocalePersistence::new(directory.path());
+    let path = persistence.path();
+
+    let mut app = app_with(persistence);
+    app.add_startup_system(|mut locale: ResMut<Locale>| locale.set("fr"));
+    app.update();
+
+    let app = app_with(LocalePersistence::new(directory.path()));
+
+    assert!(path.exists());
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("fr"));
+}
+
+#[test]
+fn fallbacks_are_only_saved_if_enabled() {
+    let directory = tempfile::tempdir().unwrap();
+    let persistence = || LocalePersistence::new(directory.path()).with_fallbacks(false);
+
+    let mut app = app_with(persistence());
+    app.world.resource_mut::<Locale>().set("de-AT");
+    app.insert_resource(fallback_map());
+    app.update();
+
+    let content = fs::read_to_string(persistence().path()).unwrap();
+    assert!(!content.contains("fallback"));
+
+    let app = app_with(persistence());
+
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("de-AT"));
+    assert_eq!(
+        *app.world.resource::<LocaleFallbackMap>(),
+        LocaleFallbackMap::new()
+    );
+}
+
+#[test]
+fn handwritten_settings_are_restored() {
+    let directory = tempfile::tempdir().unwrap();
+    fs::write(
+        directory.path().join("settings.ron"),
+        r#"(
+            locale: "ar",
+            fallback_map: Some({ "ar": [] }),
+            default_fallback: Some(None),
+        )"#,
+    )
+    .unwrap();
+
+    let app = app_with(LocalePersistence::new(directory.path()).with_file_name("settings.ron"));
+
+    let mut fallback_map = LocaleFallbackMap::new();
+    fallback_map.insert("ar", Vec::<&str>::new());
+
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("ar"));
+    assert_eq!(*app.world.resource::<LocaleFallbackMap>(), fallback_map);
+    assert_eq!(
+        *app.world.resource::<LocaleDefaultFallback>(),
+        LocaleDefaultFallback::new(None::<&str>)
+    );
+}
+
+#[test]
+fn invalid_settings_are_ignored() {
+    let directory = tempfile::tempdir().unwrap();
+    let persistence = LocalePersistence::new(directory.path());
+    fs::write(persistence.path(), "locale = de").unwrap();
+
+    let app = app_with(persistence);
+
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("en-US"));
+}
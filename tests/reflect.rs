@@ -0,0 +1,58 @@
+use std::any::TypeId;
+
+use bevy::{asset::AssetPlugin, ecs::reflect::ReflectResource, prelude::*};
+use bevy_mod_localization::prelude::*;
+
+fn app() -> App {
+    let mut app = App::new();
+
+    app.insert_resource(Locale::new("en-US"))
+        .add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(LocalizationPlugin);
+
+    app
+}
+
+fn reflect_locale(app: &App) -> ReflectResource {
+    app.world
+        .resource::<AppTypeRegistry>()
+        .read()
+        .get_type_data::<ReflectResource>(TypeId::of::<Locale>())
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn the_locale_can_be_read_and_changed_through_reflection() {
+    let mut app = app();
+    let reflect_resource = reflect_locale(&app);
+
+    let locale = reflect_resource.reflect(&app.world).unwrap();
+    assert_eq!(locale.downcast_ref::<Locale>(), Some(&Locale::new("en-US")));
+
+    reflect_resource.apply(&mut app.world, &Locale::new("de"));
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("de"));
+}
+
+#[test]
+fn the_locale_can_be_inserted_through_reflection() {
+    let mut app = app();
+    app.world.remove_resource::<Locale>();
+    let reflect_resource = reflect_locale(&app);
+
+    reflect_resource.insert(&mut app.world, &Locale::new("fr"));
+    assert_eq!(*app.world.resource::<Locale>(), Locale::new("fr"));
+}
+
+#[test]
+fn the_locale_created_from_the_world_is_the_default_fallback() {
+    let mut app = app();
+    app.insert_resource(LocaleDefaultFallback::new(Some("de")));
+
+    assert_eq!(Locale::from_world(&mut app.world), Locale::new("de"));
+
+    app.insert_resource(LocaleDefaultFallback::new(None::<&str>));
+
+    assert_eq!(Locale::from_world(&mut app.world), Locale::new("und"));
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "ron")]
+
+use bevy_mod_localization::prelude::*;
+
+#[test]
+fn fallback_map_is_serialized_as_map() {
+    let mut fallback_map = LocaleFallbackMap::new();
+    fallback_map.insert("en-GB", vec!["en-US"]);
+
+    let serialized = ron::to_string(&fallback_map).unwrap();
+    assert_eq!(serialized, r#"{"en-GB":["en-US"]}"#);
+
+    let deserialized: LocaleFallbackMap = ron::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, fallback_map);
+}
+
+#[test]
+fn transitive_fallback_map_keeps_its_mode() {
+    let mut fallback_map = LocaleFallbackMap::new();
+    fallback_map.insert("es-MX", vec!["es-419"]);
+    fallback_map.set_transitive(true);
+
+    let serialized = ron::to_string(&fallback_map).unwrap();
+    let deserialized: LocaleFallbackMap = ron::from_str(&serialized).unwrap();
+
+    assert_eq!(deserialized, fallback_map);
+}
+
+#[test]
+fn fallback_map_accepts_both_forms() {
+    let mut expected = LocaleFallbackMap::new();
+    expected.insert("en-GB", vec!["en-US"]);
+
+    let map: LocaleFallbackMap = ron::from_str(r#"{ "en-GB": ["en-US"] }"#).unwrap();
+    assert_eq!(map, expected);
+
+    let with_options: LocaleFallbackMap =
+        ron::from_str(r#"(fallbacks: { "en-GB": ["en-US"] })"#).unwrap();
+    assert_eq!(with_options, expected);
+}